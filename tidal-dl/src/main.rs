@@ -13,27 +13,10 @@ use indicatif::{
     ProgressBar,
     ProgressStyle,
 };
-use lofty::config::WriteOptions;
-use lofty::picture::{
-    MimeType,
-    Picture,
-    PictureType,
-};
-use lofty::prelude::*;
-use lofty::probe::Probe;
-use lofty::tag::{
-    ItemKey,
-    ItemValue,
-    Tag,
-    TagItem,
-    TagType,
-};
+use lofty::picture::MimeType;
 use regex::Regex;
 use reqwest::header::CONTENT_TYPE;
-use serde::{
-    Deserialize,
-    Serialize,
-};
+use serde::Serialize;
 use termcolor::{
     Color,
     ColorChoice,
@@ -42,13 +25,30 @@ use termcolor::{
     WriteColor,
 };
 use tidal::{
+    Album,
     AudioQuality,
     AuthSession,
+    Credentials,
+    FavoriteOrder,
     ImageSize,
+    Lyrics,
+    OrderDirection,
     Playlist,
+    PlaylistEntryItem,
     StreamInfo,
+    SyncedLyrics,
     TidalClient,
     Track,
+    VideoQuality,
+    verify_flac,
+};
+
+mod tagging;
+
+use tagging::{
+    LoftyTagWriter,
+    TagWriter,
+    TrackMetadata,
 };
 
 type AppResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
@@ -61,18 +61,165 @@ type AppResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
     about = "Download music from Tidal in highest quality"
 )]
 struct Args {
-    link: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    link: Option<String>,
 
     #[arg(short, long)]
     output: Option<PathBuf>,
+
+    /// Skip tracks whose resolved stream is lossy instead of saving them
+    #[arg(long)]
+    lossless_only: bool,
+
+    /// Download albums as one concatenated FLAC plus a .cue sheet
+    ///
+    /// The output is a raw byte concatenation of the per-track FLAC
+    /// streams: decoders stop at the first stream's end, so re-mux the
+    /// file (e.g. with ffmpeg) before the cue offsets past track 1 are
+    /// playable. Fails if any track resolves to a lossy stream.
+    #[arg(long)]
+    single_file: bool,
+
+    /// Write full track credits to a {basename}.credits.json sidecar
+    #[arg(long)]
+    credits_sidecar: bool,
+
+    /// Force the album artist tag to "Various Artists" for album downloads
+    #[arg(long)]
+    various_artists: bool,
+
+    /// Save the album's animated cover as cover.mp4 in the album folder
+    #[arg(long)]
+    video_cover: bool,
+
+    /// Encoding for the .lrc lyrics sidecar; some older Windows players only
+    /// detect UTF-8 when a BOM is present
+    #[arg(long, value_enum, default_value_t = LrcEncoding::Utf8)]
+    lrc_encoding: LrcEncoding,
+
+    /// After the primary download, also fetch this quality into a file with
+    /// a quality suffix (archival + portable copy in one run)
+    #[arg(long, value_enum)]
+    also_quality: Option<QualityArg>,
+
+    /// Stream quality to request: LOW, HIGH, LOSSLESS, HI_RES or
+    /// HI_RES_LOSSLESS (aliases hifi/max accepted). When Tidal refuses the
+    /// tier outright, the next lower one is tried with a warning
+    #[arg(short, long, default_value = "hi_res_lossless")]
+    quality: AudioQuality,
+
+    /// Filename template (without extension), e.g. "{track_number} - {title}".
+    /// Placeholders: {artist} {title} {album} {track_number} {year} {isrc}
+    /// {quality}; unknown ones are left literal
+    #[arg(long)]
+    template: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+enum QualityArg {
+    Low,
+    High,
+    Lossless,
+    HiRes,
+    HiResLossless,
+}
+
+impl QualityArg {
+    fn to_audio_quality(self) -> AudioQuality {
+        match self {
+            QualityArg::Low => AudioQuality::Low,
+            QualityArg::High => AudioQuality::High,
+            QualityArg::Lossless => AudioQuality::Lossless,
+            QualityArg::HiRes => AudioQuality::HiRes,
+            QualityArg::HiResLossless => AudioQuality::HiResLossless,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+enum LrcEncoding {
+    #[default]
+    Utf8,
+    Utf8Bom,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct StoredCredentials {
-    access_token: String,
-    refresh_token: String,
-    expires_at: u64,
-    country_code: String,
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Re-tag an existing local file with Tidal metadata, skipping the download
+    Tag {
+        /// Tidal track link or id
+        link: String,
+        /// Existing audio file to tag
+        file: PathBuf,
+    },
+    /// Export every favorite and playlist folder to a JSON Lines file
+    Export {
+        /// Destination file, one JSON object per line
+        #[arg(long, default_value = "library.jsonl")]
+        out: PathBuf,
+    },
+    /// Recreate favorites and playlists on this account from an export file
+    Import {
+        /// JSON Lines file produced by `export`
+        file: PathBuf,
+    },
+    /// Search Tidal and pick a result to download
+    Search {
+        /// What to search for
+        query: String,
+        /// Limit results to one category
+        #[arg(long = "type", value_enum)]
+        kind: Option<SearchKind>,
+        /// Results per category
+        #[arg(long, default_value_t = 10)]
+        limit: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum SearchKind {
+    Tracks,
+    Albums,
+    Artists,
+    Playlists,
+}
+
+#[derive(Debug, Clone)]
+struct DownloadOptions {
+    quality: AudioQuality,
+    lossless_only: bool,
+    single_file: bool,
+    credits_sidecar: bool,
+    various_artists: bool,
+    video_cover: bool,
+    lrc_encoding: LrcEncoding,
+    also_quality: Option<QualityArg>,
+    template: Option<String>,
+    /// Resolved once per album so every track gets the same AlbumArtist tag.
+    album_artist: Option<String>,
+    /// Sort prefix ("01", or "1-01" on multi-disc albums) set per track by
+    /// album downloads; not applied when a custom template is in use.
+    track_prefix: Option<String>,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            quality: AudioQuality::HiResLossless,
+            lossless_only: false,
+            single_file: false,
+            credits_sidecar: false,
+            various_artists: false,
+            video_cover: false,
+            lrc_encoding: LrcEncoding::default(),
+            also_quality: None,
+            template: None,
+            album_artist: None,
+            track_prefix: None,
+        }
+    }
 }
 
 struct Console {
@@ -136,20 +283,17 @@ fn get_config_path() -> AppResult<PathBuf> {
     Ok(app_dir.join("credentials.json"))
 }
 
-fn load_credentials() -> AppResult<Option<StoredCredentials>> {
+fn load_credentials() -> AppResult<Option<Credentials>> {
     let path = get_config_path()?;
     if !path.exists() {
         return Ok(None);
     }
-    let content = std::fs::read_to_string(&path)?;
-    let creds: StoredCredentials = serde_json::from_str(&content)?;
-    Ok(Some(creds))
+    Ok(Some(Credentials::load_from(&path)?))
 }
 
-fn save_credentials(creds: &StoredCredentials) -> AppResult<()> {
+fn save_credentials(creds: &Credentials) -> AppResult<()> {
     let path = get_config_path()?;
-    let content = serde_json::to_string_pretty(creds)?;
-    std::fs::write(&path, content)?;
+    creds.save_to(&path)?;
     Ok(())
 }
 
@@ -196,7 +340,7 @@ async fn authenticate(console: &mut Console) -> AppResult<TidalClient> {
 
     spinner.finish_and_clear();
 
-    let mut client = TidalClient::new(
+    let client = TidalClient::new(
         token.access_token.clone(),
         token.refresh_token.clone(),
         "US".to_string(),
@@ -204,10 +348,11 @@ async fn authenticate(console: &mut Console) -> AppResult<TidalClient> {
 
     let session = client.get_session().await?;
 
-    let creds = StoredCredentials {
+    let creds = Credentials {
         access_token: token.access_token,
         refresh_token: token.refresh_token,
         expires_at: current_timestamp() + token.expires_in,
+        user_id: Some(session.user_id),
         country_code: session.country_code,
     };
 
@@ -224,39 +369,21 @@ async fn get_client(console: &mut Console) -> AppResult<TidalClient> {
         None => return authenticate(console).await,
     };
 
-    if current_timestamp() + 300 > creds.expires_at {
+    // The client refreshes its own tokens from here on (proactively on
+    // expiry, and once on a stray 401); we only persist what it learned and
+    // fall back to a fresh device auth when the refresh token itself died.
+    let mut client = TidalClient::with_auto_refresh(creds, AuthSession::new());
+    if client.is_token_expired() {
         console.info("Token expired, refreshing...");
-        let auth = AuthSession::new();
-        match auth.refresh_token(&creds.refresh_token).await {
-            Ok(token) => {
-                let mut client = TidalClient::new(
-                    token.access_token.clone(),
-                    token.refresh_token.clone(),
-                    creds.country_code.clone(),
-                );
-                client.get_session().await?;
-
-                let new_creds = StoredCredentials {
-                    access_token: token.access_token,
-                    refresh_token: token.refresh_token,
-                    expires_at: current_timestamp() + token.expires_in,
-                    country_code: creds.country_code,
-                };
-                save_credentials(&new_creds)?;
-                console.success("Token refreshed.");
-                Ok(client)
-            }
-            Err(_) => {
-                console.info("Failed to refresh token. Re-authenticating...");
-                authenticate(console).await
-            }
+        if client.refresh_tokens().await.is_err() {
+            console.info("Failed to refresh token. Re-authenticating...");
+            return authenticate(console).await;
         }
-    } else {
-        let mut client =
-            TidalClient::new(creds.access_token, creds.refresh_token, creds.country_code);
-        client.get_session().await?;
-        Ok(client)
+        save_credentials(&client.to_credentials())?;
+        console.success("Token refreshed.");
     }
+    client.get_session().await?;
+    Ok(client)
 }
 
 fn parse_tidal_link(link: &str) -> AppResult<(String, String)> {
@@ -283,9 +410,79 @@ fn parse_tidal_link(link: &str) -> AppResult<(String, String)> {
         return Ok(("playlist".to_string(), id));
     }
 
+    let video_re = Regex::new(r"(?:tidal\.com|listen\.tidal\.com)(?:/browse)?/video/(\d+)")?;
+    if let Some(caps) = video_re.captures(link) {
+        let id = caps.get(1).unwrap().as_str().to_string();
+        return Ok(("video".to_string(), id));
+    }
+
+    // Mix ids are plain alphanumeric strings, not dashed UUIDs like
+    // playlists.
+    let mix_re = Regex::new(r"(?:tidal\.com|listen\.tidal\.com)(?:/browse)?/mix/([a-zA-Z0-9]+)")?;
+    if let Some(caps) = mix_re.captures(link) {
+        let id = caps.get(1).unwrap().as_str().to_string();
+        return Ok(("mix".to_string(), id));
+    }
+
     Err(format!("Could not parse Tidal link: {}", link).into())
 }
 
+/// Render a filename template (extension excluded; it's appended from the
+/// stream). Every substituted value runs through `sanitize_filename`
+/// individually, so a track titled "AC/DC" can't escape its folder. Unknown
+/// placeholders stay literal, which makes a typo visible in the resulting
+/// filename instead of silently vanishing.
+fn format_template(template: &str, track: &Track, full_title: &str, quality: &str) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}') else {
+            rest = &rest[open..];
+            break;
+        };
+        let key = &rest[open + 1..open + close];
+        let value = match key {
+            "artist" => Some(
+                track
+                    .artist
+                    .as_ref()
+                    .map(|a| a.name.clone())
+                    .or_else(|| track.artists.first().map(|a| a.name.clone()))
+                    .unwrap_or_else(|| "Unknown Artist".to_string()),
+            ),
+            "title" => Some(full_title.to_string()),
+            "album" => Some(
+                track
+                    .album
+                    .as_ref()
+                    .map(|a| a.title.clone())
+                    .unwrap_or_default(),
+            ),
+            "track_number" => Some(format!("{:02}", track.track_number.unwrap_or(0))),
+            "year" => Some(
+                track
+                    .album
+                    .as_ref()
+                    .and_then(|a| a.release_year())
+                    .map(|y| y.to_string())
+                    .unwrap_or_default(),
+            ),
+            "isrc" => Some(track.isrc.clone().unwrap_or_default()),
+            "quality" => Some(quality.to_string()),
+            _ => None,
+        };
+        match value {
+            Some(value) => out.push_str(&sanitize_filename(&value)),
+            None => out.push_str(&rest[open..open + close + 1]),
+        }
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
 fn sanitize_filename(name: &str) -> String {
     let invalid_chars = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
     let mut result = name.to_string();
@@ -296,37 +493,107 @@ fn sanitize_filename(name: &str) -> String {
 }
 
 fn format_duration(seconds: u32) -> String {
-    let mins = seconds / 60;
+    let hours = seconds / 3600;
+    let mins = (seconds % 3600) / 60;
     let secs = seconds % 60;
-    format!("{}:{:02}", mins, secs)
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, mins, secs)
+    } else {
+        format!("{}:{:02}", mins, secs)
+    }
+}
+
+/// The two lyric representations a single `Lyrics` response carries: plain
+/// text, and timestamped lyrics normalized to LRC for the sidecar.
+#[derive(Default)]
+struct LyricsContent {
+    plain: Option<String>,
+    synced: Option<String>,
+}
+
+impl LyricsContent {
+    /// Split a lyrics response into its two representations. Tidal serves
+    /// subtitles as either LRC or TTML; timestamped lyrics are normalized to
+    /// LRC, and anything the parser doesn't recognize is kept raw rather
+    /// than dropped.
+    fn from_response(lyrics: Lyrics) -> Self {
+        Self {
+            plain: lyrics.lyrics.filter(|c| !c.is_empty()),
+            synced: lyrics.subtitles.filter(|c| !c.is_empty()).map(|raw| {
+                match SyncedLyrics::parse(&raw) {
+                    Some(parsed) => parsed.to_lrc(),
+                    None => raw,
+                }
+            }),
+        }
+    }
+
+    /// What to embed in the lyrics tag: the synced LRC when there is one
+    /// (players that understand timestamps sync it, the rest show it as
+    /// text), falling back to plain lyrics.
+    fn for_embedding(&self) -> Option<String> {
+        self.synced.clone().or_else(|| self.plain.clone())
+    }
+}
+
+/// Prefix each lyric line with a right-to-left mark so players that don't do
+/// bidi detection render RTL scripts in the correct direction. For synced
+/// lines the mark goes after the timestamp, which must stay left-to-right.
+fn apply_rtl_marks(text: &str) -> String {
+    const RLM: char = '\u{200F}';
+    text.lines()
+        .map(|line| {
+            if let Some(bracket) = line.rfind(']') {
+                format!("{}{}{}", &line[..=bracket], RLM, &line[bracket + 1..])
+            } else if line.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", RLM, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 async fn download_lyrics(
     client: &mut TidalClient,
     track_id: u64,
     output_path: &PathBuf,
+    encoding: LrcEncoding,
     console: &mut Console,
-) -> AppResult<Option<String>> {
+) -> AppResult<LyricsContent> {
     console.status("Fetching lyrics... ");
 
     match client.get_lyrics(track_id).await {
         Ok(lyrics) => {
-            let content = lyrics.subtitles.or(lyrics.lyrics).unwrap_or_default();
+            let right_to_left = lyrics.is_right_to_left == Some(true);
+            let content = LyricsContent::from_response(lyrics);
 
-            if content.is_empty() {
+            // The sidecar gets the synced version when there is one; plain
+            // lyrics are still better than no .lrc at all.
+            let Some(sidecar) = content.synced.as_ref().or(content.plain.as_ref()) else {
                 console.println_colored("not available", Color::Yellow);
-                return Ok(None);
+                return Ok(content);
+            };
+
+            let mut text = if right_to_left {
+                apply_rtl_marks(sidecar)
+            } else {
+                sidecar.clone()
+            };
+            if encoding == LrcEncoding::Utf8Bom {
+                text.insert(0, '\u{FEFF}');
             }
 
-            tokio::fs::write(output_path, &content).await?;
+            tokio::fs::write(output_path, text).await?;
             console.println_colored("OK", Color::Green);
             console.print("  Saved: ");
             console.println_colored(&output_path.display().to_string(), Color::Cyan);
-            Ok(Some(content))
+            Ok(content)
         }
         Err(_) => {
             console.println_colored("not available", Color::Yellow);
-            Ok(None)
+            Ok(LyricsContent::default())
         }
     }
 }
@@ -411,383 +678,258 @@ fn detect_container(data: &[u8]) -> ContainerKind {
     ContainerKind::Flac
 }
 
-async fn embed_metadata(
-    client: &mut TidalClient,
-    output_path: &Path,
-    track: &Track,
-    full_title: &str,
-    stream_info: &StreamInfo,
-    lyrics: Option<String>,
-) -> AppResult<()> {
-    let ext = output_path
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or_default()
-        .to_ascii_lowercase();
+/// Gather everything the tag writer needs: the full album, album-header
+/// credits, cover art, and the rendered encoder/quality description.
+fn build_encoder_settings(track: &Track, stream_info: &StreamInfo) -> Option<String> {
+    let mut encoder_info_parts = Vec::new();
 
-    let tag_type = if ext == "flac" {
-        TagType::VorbisComments
+    // Only claim the track's advertised quality when the stream actually is
+    // lossless; a LOW/HIGH download shouldn't be tagged as hi-res.
+    if stream_info.is_lossless() {
+        if let Some(quality) = track
+            .audio_quality
+            .as_ref()
+            .or_else(|| track.album.as_ref().and_then(|a| a.audio_quality.as_ref()))
+        {
+            encoder_info_parts.push(format!("Tidal {}", quality));
+        }
     } else {
-        TagType::Mp4Ilst
-    };
-
-    let mut tagged_file = Probe::open(output_path)?.read()?;
-    if tagged_file.tag(tag_type).is_none() {
-        tagged_file.insert_tag(Tag::new(tag_type));
+        encoder_info_parts.push(format!("Tidal {}", stream_info.codec_display()));
     }
 
-    let tag = tagged_file
-        .tag_mut(tag_type)
-        .ok_or_else(|| "Failed to get tag".to_string())?;
-
-    let artists_joined = track
-        .artists
-        .iter()
-        .map(|a| a.name.as_str())
-        .collect::<Vec<_>>()
-        .join(", ");
-
-    tag.set_title(full_title.to_string());
-    tag.set_artist(artists_joined.clone());
-
-    if let Some(version) = track.version.as_ref() {
-        tag.insert_text(ItemKey::TrackSubtitle, version.clone());
+    if let Some(details) = encode_audio_details(stream_info) {
+        encoder_info_parts.push(details);
     }
 
-    if let Some(album) = &track.album {
-        if let Some(album_artist) = album.primary_artist() {
-            tag.insert_text(ItemKey::AlbumArtist, album_artist.name.clone());
-        } else if let Some(primary) = track.primary_artist() {
-            tag.insert_text(ItemKey::AlbumArtist, primary.name.clone());
-        } else {
-            tag.insert_text(ItemKey::AlbumArtist, artists_joined.clone());
+    if let Some(modes) = track.audio_modes.as_ref()
+        && !modes.is_empty() {
+            encoder_info_parts.push(format!("Modes: {}", modes.join(", ")));
         }
-    } else if let Some(primary) = track.primary_artist() {
-        tag.insert_text(ItemKey::AlbumArtist, primary.name.clone());
-    } else {
-        tag.insert_text(ItemKey::AlbumArtist, artists_joined.clone());
-    }
-
-    tag.insert_text(ItemKey::Performer, artists_joined.clone());
-    tag.insert_text(ItemKey::OriginalArtist, artists_joined.clone());
 
-    if let Some(primary) = track.primary_artist() {
-        tag.insert_text(ItemKey::Composer, primary.name.clone());
+    if encoder_info_parts.is_empty() {
+        None
     } else {
-        tag.insert_text(ItemKey::Composer, artists_joined.clone());
-    }
-
-    for artist in &track.artists {
-        tag.push(TagItem::new(
-            ItemKey::TrackArtists,
-            ItemValue::Text(artist.name.clone()),
-        ));
-    }
-
-    if let Some(tags) = track
-        .media_metadata
-        .as_ref()
-        .and_then(|m| m.tags.as_ref())
-        .filter(|v| !v.is_empty())
-        .or_else(|| {
-            track
-                .album
-                .as_ref()
-                .and_then(|a| a.media_metadata.as_ref())
-                .and_then(|m| m.tags.as_ref())
-                .filter(|v| !v.is_empty())
-        })
-    {
-        let genres = tags.join(", ");
-        tag.insert_text(ItemKey::Genre, genres);
+        Some(encoder_info_parts.join(" | "))
     }
+}
 
-    let date_to_use = track
-        .album
-        .as_ref()
-        .and_then(|a| a.release_date.as_ref().or(a.stream_start_date.as_ref()))
-        .or(track.stream_start_date.as_ref());
-
-    if let Some(date) = date_to_use {
-        if let Some(year_str) = date.split('-').next() {
-            if let Ok(y) = year_str.parse::<u32>() {
-                tag.set_year(y);
-                tag.insert_text(ItemKey::Year, year_str.to_string());
-
-                let date_only = date.split('T').next().unwrap_or(date);
-                tag.insert_text(ItemKey::RecordingDate, date_only.to_string());
-                tag.insert_text(ItemKey::ReleaseDate, date_only.to_string());
-                tag.insert_text(ItemKey::OriginalReleaseDate, date_only.to_string());
-            }
-        }
-    }
-
-    if let Some(album) = &track.album {
-        tag.set_album(album.title.clone());
-
-        match client.get_album(album.id).await {
-            Ok(full_album) => {
-                if let Some(total) = full_album.number_of_tracks {
-                    tag.set_track_total(total);
-                }
-
-                if let Some(vol_total) = full_album.number_of_volumes {
-                    tag.set_disk_total(vol_total);
-                }
-            }
-            Err(_) => {
-                if let Some(total) = album.number_of_tracks {
-                    tag.set_track_total(total);
-                }
-
-                if let Some(vol_total) = album.number_of_volumes {
-                    tag.set_disk_total(vol_total);
-                }
-            }
+async fn assemble_track_metadata(
+    client: &mut TidalClient,
+    track: &Track,
+    full_title: &str,
+    stream_info: &StreamInfo,
+    lyrics: Option<String>,
+    album_artist: Option<&str>,
+) -> AppResult<TrackMetadata> {
+    // Some endpoints return tracks with `album: None` (standalone singles,
+    // certain playlist items), which would silently skip cover art, album
+    // artist and totals. Re-fetching the track usually fills the album in.
+    let track = if track.album.is_none() {
+        match client.get_track(track.id).await {
+            Ok(refetched) if refetched.album.is_some() => refetched,
+            _ => track.clone(),
         }
+    } else {
+        track.clone()
+    };
+    let track = &track;
 
-        if let Some(upc) = album.upc.clone() {
-            tag.insert_text(ItemKey::CatalogNumber, upc.clone());
-            tag.insert_text(ItemKey::Barcode, upc);
-        }
+    let album = match track.album.as_ref() {
+        Some(album) => client.get_album(album.id).await.ok(),
+        None => None,
+    };
 
-        if let Some(album_type) = album.album_type.as_ref() {
-            tag.insert_text(ItemKey::OriginalMediaType, album_type.clone());
+    let credits = if let Some(album) = &track.album {
+        match client.get_album_page(album.id).await {
+            Ok(album_page) => album_page
+                .rows
+                .iter()
+                .flat_map(|row| &row.modules)
+                .find(|module| module.module_type == "ALBUM_HEADER")
+                .and_then(|module| module.credits.as_ref())
+                .map(|c| c.items.clone())
+                .unwrap_or_default(),
+            Err(_) => Vec::new(),
         }
-    }
-
-    if let Some(n) = track.track_number {
-        tag.set_track(n);
-    }
-
-    if let Some(disc) = track.volume_number {
-        tag.set_disk(disc);
-    }
-
-    if let Some(isrc) = track.isrc.clone() {
-        tag.insert_text(ItemKey::Isrc, isrc);
-    }
-
-    if let Some(url) = track.url.as_ref() {
-        tag.insert_text(ItemKey::AudioSourceUrl, url.clone());
-    }
-
-    if track.explicit {
-        tag.insert_text(ItemKey::ParentalAdvisory, "Explicit".to_string());
-    }
-
-    if let Some(gain) = track.replay_gain {
-        tag.insert_text(ItemKey::ReplayGainTrackGain, format!("{gain:.2} dB"));
-    }
+    } else {
+        Vec::new()
+    };
 
-    if let Some(peak) = track.peak {
-        tag.insert_text(ItemKey::ReplayGainTrackPeak, format!("{peak:.6}"));
-    }
+    let cover = fetch_cover_image(track).await?;
 
-    let mut encoder_info_parts = Vec::new();
+    let encoder_settings = build_encoder_settings(track, stream_info);
 
-    if let Some(quality) = track
-        .audio_quality
-        .as_ref()
-        .or_else(|| track.album.as_ref().and_then(|a| a.audio_quality.as_ref()))
-    {
-        encoder_info_parts.push(format!("Tidal {}", quality));
-    }
+    Ok(TrackMetadata {
+        track: track.clone(),
+        full_title: full_title.to_string(),
+        album,
+        album_artist: album_artist.map(str::to_string),
+        lyrics,
+        cover,
+        credits,
+        encoder_settings,
+    })
+}
 
-    if let Some(details) = encode_audio_details(stream_info) {
-        encoder_info_parts.push(details);
-    }
+async fn embed_metadata(
+    client: &mut TidalClient,
+    output_path: &Path,
+    track: &Track,
+    full_title: &str,
+    stream_info: &StreamInfo,
+    lyrics: Option<String>,
+    album_artist: Option<&str>,
+) -> AppResult<()> {
+    let metadata =
+        assemble_track_metadata(client, track, full_title, stream_info, lyrics, album_artist)
+            .await?;
+    LoftyTagWriter.write(output_path, &metadata)
+}
 
-    if let Some(modes) = track.audio_modes.as_ref() {
-        if !modes.is_empty() {
-            encoder_info_parts.push(format!("Modes: {}", modes.join(", ")));
+/// Compare the saved file's parsed stream duration against Tidal's track
+/// duration and warn when they diverge by more than a few seconds — the
+/// signature of dropped segments or a wrong-length download, which size
+/// checks alone miss. A file lofty can't parse at all gets a warning too.
+fn verify_duration(path: &Path, expected_secs: u32, console: &mut Console) {
+    use lofty::prelude::AudioFile;
+
+    const TOLERANCE_SECS: i64 = 3;
+
+    let actual_secs = match lofty::read_from_path(path) {
+        Ok(file) => file.properties().duration().as_secs() as i64,
+        Err(e) => {
+            console.println_colored(
+                &format!("Warning: could not verify duration ({})", e),
+                Color::Yellow,
+            );
+            return;
         }
-    }
-
-    if !encoder_info_parts.is_empty() {
-        tag.insert_text(ItemKey::EncoderSettings, encoder_info_parts.join(" | "));
-    }
-
-    tag.insert_text(ItemKey::EncoderSoftware, "Tidal".to_string());
-
-    if let Some(media_tags) = track
-        .media_metadata
-        .as_ref()
-        .and_then(|m| m.tags.as_ref())
-        .filter(|t| !t.is_empty())
-    {
-        let tags_str = media_tags.join(", ");
-        tag.insert_text(ItemKey::Description, format!("Quality: {}", tags_str));
-    }
+    };
 
-    if let Some(popularity) = track.popularity {
-        tag.insert_text(ItemKey::Popularimeter, popularity.to_string());
+    let diff = actual_secs - i64::from(expected_secs);
+    if diff.abs() > TOLERANCE_SECS {
+        console.println_colored(
+            &format!(
+                "Warning: file duration is {}s but Tidal reports {}s; the \
+                 download may be truncated",
+                actual_secs, expected_secs
+            ),
+            Color::Yellow,
+        );
     }
+}
 
-    if let Some(c) = track
-        .copyright
-        .clone()
-        .or_else(|| track.album.as_ref().and_then(|a| a.copyright.clone()))
-    {
-        tag.insert_text(ItemKey::CopyrightMessage, c);
-    }
+/// Download every segment of `stream_info`, decrypting in order, with a bar
+/// showing rolling speed and ETA. Single-file streams get a byte-accurate bar
+/// from one HEAD request; multi-segment DASH streams fall back to
+/// segment-count progress so we don't pay a HEAD per segment up front.
+async fn download_with_progress(
+    client: &TidalClient,
+    stream_info: &mut StreamInfo,
+) -> AppResult<Vec<u8>> {
+    let total_bytes = if stream_info.urls.len() == 1 {
+        client
+            .estimate_download_size(stream_info)
+            .await
+            .ok()
+            .filter(|&t| t > 0)
+    } else {
+        None
+    };
 
-    if let Some(album) = &track.album {
-        if let Some(label_artist) = album.artist.as_ref() {
-            tag.insert_text(ItemKey::Label, label_artist.name.clone());
-            tag.insert_text(ItemKey::Publisher, label_artist.name.clone());
+    let pb = match total_bytes {
+        Some(total) => {
+            let pb = ProgressBar::new(total);
+            pb.set_style(
+                ProgressStyle::default_bar()
+                    .template("{bar:30.cyan} {bytes}/{total_bytes} {bytes_per_sec} eta {eta}")
+                    .unwrap(),
+            );
+            pb
         }
-    }
-
-    tag.insert_text(ItemKey::EncodedBy, "Tidal".to_string());
-
-    if let Some(key) = track.musical_key_formatted() {
-        tag.insert_text(ItemKey::InitialKey, key);
-    }
-
-    if let Some(bpm) = track.bpm {
-        tag.insert_text(ItemKey::Bpm, bpm.to_string());
-        tag.insert_text(ItemKey::IntegerBpm, bpm.to_string());
-    }
-
-    let mut comment_parts = Vec::new();
+        None => {
+            let pb = ProgressBar::new(stream_info.urls.len() as u64);
+            pb.set_style(
+                ProgressStyle::default_bar()
+                    .template("{bar:30.cyan} {pos}/{len} segments eta {eta}")
+                    .unwrap(),
+            );
+            pb
+        }
+    };
 
-    if let Some(popularity) = track.popularity {
-        comment_parts.push(format!("Popularity: {}/100", popularity));
-    }
+    let http = reqwest::Client::new();
+    let urls = stream_info.urls.clone();
+    let mut data = Vec::new();
 
-    if track.stream_ready == Some(true) {
-        if let Some(start_date) = track.stream_start_date.as_ref() {
-            if let Some(date_only) = start_date.split('T').next() {
-                comment_parts.push(format!("Available since: {}", date_only));
+    for url in &urls {
+        let mut resp = http.get(url).send().await?;
+        let mut segment = Vec::new();
+        while let Some(chunk) = resp.chunk().await? {
+            if total_bytes.is_some() {
+                pb.inc(chunk.len() as u64);
             }
+            segment.extend_from_slice(&chunk);
         }
-    }
 
-    comment_parts.push(format!("Tidal ID: {}", track.id));
-
-    if !comment_parts.is_empty() {
-        let comment = comment_parts.join(" | ");
-        if let Some(existing) = tag.get_string(&ItemKey::Comment) {
-            tag.insert_text(ItemKey::Comment, format!("{} | {}", existing, comment));
-        } else {
-            tag.insert_text(ItemKey::Comment, comment);
+        if let Some(ref mut decryptor) = stream_info.encryption {
+            decryptor.decrypt(&mut segment);
         }
-    }
-
-    if let Some(text) = lyrics.clone() {
-        tag.insert_text(ItemKey::Lyrics, text);
-    }
+        data.extend(segment);
 
-    let credits = if let Some(album) = &track.album {
-        match client.get_album_page(album.id).await {
-            Ok(album_page) => {
-                let album_credits = album_page
-                    .rows
-                    .iter()
-                    .flat_map(|row| &row.modules)
-                    .find(|module| module.module_type == "ALBUM_HEADER")
-                    .and_then(|module| module.credits.as_ref())
-                    .map(|c| &c.items);
-
-                album_credits.map(|c| c.clone())
-            }
-            Err(_) => None,
+        if total_bytes.is_none() {
+            pb.inc(1);
         }
-    } else {
-        None
-    };
-
-    if let Some(credits) = credits {
-        for credit in credits.iter() {
-            let contributors = credit
-                .contributors
-                .iter()
-                .map(|c| c.name.as_str())
-                .collect::<Vec<_>>()
-                .join(", ");
+    }
 
-            if contributors.is_empty() {
-                continue;
-            }
+    pb.finish_and_clear();
+    Ok(data)
+}
 
-            let credit_type_lower = credit.credit_type.to_lowercase();
+/// The tier to retry at when Tidal refuses a quality outright (as opposed to
+/// silently downgrading, which `was_downgraded` reports).
+fn next_lower_quality(quality: &AudioQuality) -> Option<AudioQuality> {
+    match quality {
+        AudioQuality::HiResLossless => Some(AudioQuality::HiRes),
+        AudioQuality::HiRes => Some(AudioQuality::Lossless),
+        AudioQuality::Lossless => Some(AudioQuality::High),
+        AudioQuality::High => Some(AudioQuality::Low),
+        AudioQuality::Low => None,
+    }
+}
 
-            match credit_type_lower.as_str() {
-                "producer" | "producers" => {
-                    tag.insert_text(ItemKey::Producer, contributors);
-                }
-                "mixer" | "mixing" | "mix engineer" => {
-                    tag.insert_text(ItemKey::MixEngineer, contributors);
-                }
-                "engineer" | "recording engineer" | "audio engineer" => {
-                    tag.insert_text(ItemKey::Engineer, contributors);
-                }
-                "writer" | "songwriter" => {
-                    tag.insert_text(ItemKey::Writer, contributors);
-                }
-                "composer" | "composers" => {
-                    if tag.get_string(&ItemKey::Composer).is_none() {
-                        tag.insert_text(ItemKey::Composer, contributors);
-                    }
-                }
-                "lyricist" => {
-                    tag.insert_text(ItemKey::Lyricist, contributors);
-                }
-                "arranger" => {
-                    tag.insert_text(ItemKey::Arranger, contributors);
-                }
-                "conductor" => {
-                    tag.insert_text(ItemKey::Conductor, contributors);
-                }
-                "remixer" | "remix" => {
-                    tag.insert_text(ItemKey::Remixer, contributors);
-                }
-                "performer" | "performers" => {
-                    let performer_info = format!("Performers: {}", contributors);
-                    if let Some(existing_comment) = tag.get_string(&ItemKey::Comment) {
-                        tag.insert_text(
-                            ItemKey::Comment,
-                            format!("{} | {}", existing_comment, performer_info),
-                        );
-                    } else {
-                        tag.insert_text(ItemKey::Comment, performer_info);
-                    }
-                }
-                "record label" => {
-                    tag.insert_text(ItemKey::Label, contributors.clone());
-                    tag.insert_text(ItemKey::Publisher, contributors);
-                }
-                _ => {
-                    let credit_info = format!("{}: {}", credit.credit_type, contributors);
-                    if let Some(existing_comment) = tag.get_string(&ItemKey::Comment) {
-                        tag.insert_text(
-                            ItemKey::Comment,
-                            format!("{} | {}", existing_comment, credit_info),
-                        );
-                    } else {
-                        tag.insert_text(ItemKey::Comment, credit_info);
-                    }
+/// Fetch stream info at the requested quality, walking down the tiers with a
+/// warning when a tier is refused. Only the bottom tier's error propagates.
+async fn fetch_stream_info_with_fallback(
+    client: &mut TidalClient,
+    track_id: u64,
+    quality: &AudioQuality,
+    console: &mut Console,
+) -> AppResult<StreamInfo> {
+    let mut quality = quality.clone();
+    loop {
+        console.status(&format!("Fetching stream info ({})... ", quality));
+        match client.get_stream_info(track_id, quality.clone()).await {
+            Ok(info) => return Ok(info),
+            Err(e) => match next_lower_quality(&quality) {
+                Some(lower) => {
+                    console.println_colored(
+                        &format!("unavailable ({}); trying {}", e, lower),
+                        Color::Yellow,
+                    );
+                    quality = lower;
                 }
-            }
+                None => return Err(e.into()),
+            },
         }
     }
-
-    if let Some((cover_bytes, mime)) = fetch_cover_image(track).await? {
-        let picture =
-            Picture::new_unchecked(PictureType::CoverFront, Some(mime), None, cover_bytes);
-        tag.push_picture(picture);
-    }
-
-    tagged_file.save_to_path(output_path, WriteOptions::default())?;
-
-    Ok(())
 }
 
 async fn download_track(
     client: &mut TidalClient,
     track: &Track,
-    output_dir: &PathBuf,
+    output_dir: &Path,
+    opts: &DownloadOptions,
     console: &mut Console,
 ) -> AppResult<()> {
     let artist_name = track
@@ -808,14 +950,19 @@ async fn download_track(
         format_duration(track.duration)
     ));
 
-    console.status("Fetching stream info... ");
-    let mut stream_info = client
-        .get_stream_info(track.id, AudioQuality::HiResLossless)
-        .await?;
+    let mut stream_info = fetch_stream_info_with_fallback(client, track.id, &opts.quality, console).await?;
+
+    if opts.lossless_only && !stream_info.is_lossless() {
+        console.println_colored(
+            &format!("skipped (lossy stream: {})", stream_info.codecs),
+            Color::Yellow,
+        );
+        return Ok(());
+    }
 
     let quality_info = format!(
         "{} {}{}",
-        stream_info.codecs,
+        stream_info.codec_display(),
         stream_info
             .sample_rate
             .map(|r| format!("{}kHz", r / 1000))
@@ -827,60 +974,385 @@ async fn download_track(
     );
     console.println_colored(&format!("OK ({})", quality_info), Color::Green);
 
-    console.status("Downloading... ");
+    if stream_info.was_downgraded() {
+        console.println_colored(
+            &format!(
+                "  Note: requested {}, got {} (subscription or catalog limit)",
+                stream_info.requested_quality, stream_info.actual_quality
+            ),
+            Color::Yellow,
+        );
+    }
 
-    let pb = ProgressBar::new_spinner();
-    pb.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.cyan} {msg}")
-            .unwrap(),
-    );
-    pb.set_message("downloading...");
-    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+    let stem = match &opts.template {
+        Some(template) => format_template(template, track, &full_title, &stream_info.actual_quality),
+        None => {
+            let base = format!(
+                "{} - {}",
+                sanitize_filename(&artist_name),
+                sanitize_filename(&full_title)
+            );
+            match &opts.track_prefix {
+                Some(prefix) => format!("{} - {}", prefix, base),
+                None => base,
+            }
+        }
+    };
+
+    // When the manifest identifies the container we know the output path
+    // before downloading, so an existing file can be skipped cheaply.
+    let expected_ext = stream_info.expected_extension();
+    if let Some(ext) = expected_ext {
+        let output_path = output_dir.join(format!("{}.{}", stem, ext));
+        if output_path.exists() {
+            console.println_colored(
+                &format!("skipped (already exists: {})", output_path.display()),
+                Color::Yellow,
+            );
+            return Ok(());
+        }
+    }
+
+    console.status("Downloading... ");
 
-    let data = client.get_stream_bytes(&mut stream_info).await?;
+    let data = download_with_progress(client, &mut stream_info).await?;
     let size_mb = data.len() as f64 / (1024.0 * 1024.0);
 
-    pb.finish_and_clear();
     console.println_colored(&format!("OK ({:.2} MB)", size_mb), Color::Green);
 
-    let container = detect_container(&data);
-    let ext = match container {
-        ContainerKind::Flac => "flac",
-        ContainerKind::Mp4 => "m4a",
+    let ext = match expected_ext {
+        Some(ext) => ext,
+        // Ambiguous manifest: fall back to sniffing the downloaded bytes.
+        None => match detect_container(&data) {
+            ContainerKind::Flac => "flac",
+            ContainerKind::Mp4 => "m4a",
+        },
     };
 
-    let filename = format!(
-        "{} - {}.{}",
-        sanitize_filename(&artist_name),
-        sanitize_filename(&full_title),
-        ext
-    );
+    let filename = format!("{}.{}", stem, ext);
     let output_path = output_dir.join(&filename);
 
     console.status("Saving... ");
     tokio::fs::write(&output_path, &data).await?;
     console.println_colored("OK", Color::Green);
 
+    verify_duration(&output_path, track.duration, console);
+
     console.print("  Saved: ");
     console.println_colored(&output_path.display().to_string(), Color::Cyan);
 
-    let lyrics_filename = format!(
-        "{} - {}.lrc",
-        sanitize_filename(&artist_name),
-        sanitize_filename(&full_title)
-    );
-    let lyrics_path = output_dir.join(&lyrics_filename);
-    let lyrics_content = download_lyrics(client, track.id, &lyrics_path, console).await?;
-
-    console.status("Embedding metadata... ");
-    embed_metadata(
+    if opts.credits_sidecar {
+        console.status("Fetching credits... ");
+        match client.get_track_credits(track.id).await {
+            Ok(credits) if !credits.is_empty() => {
+                let sidecar_path = output_path.with_extension("credits.json");
+                let json = serde_json::to_string_pretty(&credits)?;
+                tokio::fs::write(&sidecar_path, json).await?;
+                console.println_colored("OK", Color::Green);
+                console.print("  Saved: ");
+                console.println_colored(&sidecar_path.display().to_string(), Color::Cyan);
+            }
+            _ => {
+                console.println_colored("not available", Color::Yellow);
+            }
+        }
+    }
+
+    let lyrics_path = output_dir.join(format!("{}.lrc", stem));
+    let lyrics_content =
+        download_lyrics(client, track.id, &lyrics_path, opts.lrc_encoding, console).await?;
+
+    console.status("Embedding metadata... ");
+    let metadata = assemble_track_metadata(
         client,
-        &output_path,
         track,
         &full_title,
         &stream_info,
+        lyrics_content.for_embedding(),
+        opts.album_artist.as_deref(),
+    )
+    .await?;
+    LoftyTagWriter.write(&output_path, &metadata)?;
+    console.println_colored("OK", Color::Green);
+
+    if let Some(quality) = opts.also_quality {
+        download_also_quality(
+            client,
+            track,
+            &artist_name,
+            &full_title,
+            output_dir,
+            quality,
+            &metadata,
+            console,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Fetch a second copy of the track at `quality`, saved with a quality suffix
+/// in the filename. Reuses the metadata already assembled for the primary
+/// download, so the extra copy costs no API calls beyond its own stream; only
+/// the encoder-settings tag is rebuilt to describe the secondary stream.
+#[allow(clippy::too_many_arguments)]
+async fn download_also_quality(
+    client: &mut TidalClient,
+    track: &Track,
+    artist_name: &str,
+    full_title: &str,
+    output_dir: &Path,
+    quality: QualityArg,
+    metadata: &TrackMetadata,
+    console: &mut Console,
+) -> AppResult<()> {
+    console.status("Fetching secondary stream info... ");
+    let mut stream_info = client
+        .get_stream_info(track.id, quality.to_audio_quality())
+        .await?;
+    console.println_colored(
+        &format!(
+            "OK ({} {})",
+            stream_info.codec_display(),
+            stream_info.actual_quality
+        ),
+        Color::Green,
+    );
+
+    console.status("Downloading... ");
+    let data = download_with_progress(client, &mut stream_info).await?;
+    let size_mb = data.len() as f64 / (1024.0 * 1024.0);
+    console.println_colored(&format!("OK ({:.2} MB)", size_mb), Color::Green);
+
+    let ext = match stream_info.expected_extension() {
+        Some(ext) => ext,
+        None => match detect_container(&data) {
+            ContainerKind::Flac => "flac",
+            ContainerKind::Mp4 => "m4a",
+        },
+    };
+
+    let filename = format!(
+        "{} - {} [{}].{}",
+        sanitize_filename(artist_name),
+        sanitize_filename(full_title),
+        stream_info.actual_quality,
+        ext
+    );
+    let output_path = output_dir.join(&filename);
+
+    console.status("Saving... ");
+    tokio::fs::write(&output_path, &data).await?;
+    console.println_colored("OK", Color::Green);
+
+    console.print("  Saved: ");
+    console.println_colored(&output_path.display().to_string(), Color::Cyan);
+
+    let mut metadata = metadata.clone();
+    metadata.encoder_settings = build_encoder_settings(track, &stream_info);
+    LoftyTagWriter.write(&output_path, &metadata)?;
+
+    Ok(())
+}
+
+fn sort_album_tracks(tracks: &mut [Track]) {
+    // The API usually returns disc/track order already, but multi-disc
+    // releases are occasionally shuffled; sort explicitly so numbering and
+    // filenames stay correct.
+    tracks.sort_by_key(|t| (t.volume_number.unwrap_or(1), t.track_number.unwrap_or(u32::MAX)));
+}
+
+fn resolve_album_artist(album: &Album, tracks: &[Track], force_various: bool) -> String {
+    if force_various {
+        return "Various Artists".to_string();
+    }
+
+    if let Some(artist) = album.primary_artist() {
+        return artist.name.clone();
+    }
+
+    // No album-level artist: treat it as a compilation when the tracks
+    // disagree about their primary artist.
+    let mut names = tracks
+        .iter()
+        .filter_map(|t| t.primary_artist().map(|a| a.name.as_str()));
+    match names.next() {
+        Some(first) if names.all(|n| n == first) => first.to_string(),
+        Some(_) => "Various Artists".to_string(),
+        None => "Unknown Artist".to_string(),
+    }
+}
+
+fn cue_timestamp(seconds: u32) -> String {
+    format!("{:02}:{:02}:00", seconds / 60, seconds % 60)
+}
+
+fn build_cue_sheet(album_title: &str, artist_name: &str, file_name: &str, tracks: &[Track]) -> String {
+    let mut cue = String::new();
+    cue.push_str(&format!("PERFORMER \"{}\"\n", artist_name));
+    cue.push_str(&format!("TITLE \"{}\"\n", album_title));
+    cue.push_str(&format!("FILE \"{}\" WAVE\n", file_name));
+
+    let mut offset = 0u32;
+    for (i, track) in tracks.iter().enumerate() {
+        let performer = track
+            .primary_artist()
+            .map(|a| a.name.as_str())
+            .unwrap_or(artist_name);
+        cue.push_str(&format!("  TRACK {:02} AUDIO\n", i + 1));
+        cue.push_str(&format!(
+            "    TITLE \"{}\"\n",
+            build_full_title(&track.title, track.version.as_deref())
+        ));
+        cue.push_str(&format!("    PERFORMER \"{}\"\n", performer));
+        cue.push_str(&format!("    INDEX 01 {}\n", cue_timestamp(offset)));
+        offset += track.duration;
+    }
+
+    cue
+}
+
+async fn download_album_single_file(
+    client: &mut TidalClient,
+    album: &Album,
+    artist_name: &str,
+    tracks: &[Track],
+    output_dir: &Path,
+    opts: &DownloadOptions,
+    console: &mut Console,
+) -> AppResult<()> {
+    let base_name = sanitize_filename(&format!("{} - {}", artist_name, album.title));
+    let file_name = format!("{}.flac", base_name);
+    let output_path = output_dir.join(&file_name);
+
+    let mut data = Vec::new();
+    let total = tracks.len();
+
+    for (i, track) in tracks.iter().enumerate() {
+        console.println("");
+        console.println(&format!("[{}/{}] {}", i + 1, total, track.display_title()));
+        console.status("Downloading... ");
+
+        let mut stream_info = client
+            .get_stream_info(track.id, opts.quality.clone())
+            .await?;
+
+        // The output is declared .flac and the cue offsets assume every
+        // track made it in whole; silently splicing an AAC grant (subscription
+        // cap, catalog gap) into the file would corrupt it.
+        if !stream_info.is_lossless() {
+            return Err(format!(
+                "\"{}\" resolved to a lossy stream ({}); --single-file \
+                 concatenates FLAC only. Re-run without --single-file to \
+                 download per-track files instead.",
+                track.display_title(),
+                stream_info.codecs
+            )
+            .into());
+        }
+
+        let track_data = client.get_stream_bytes(&mut stream_info).await?;
+        let size_mb = track_data.len() as f64 / (1024.0 * 1024.0);
+
+        if i == 0 && let Err(e) = verify_flac(&track_data) {
+            console.println_colored(
+                &format!("Warning: first track does not verify as FLAC: {}", e),
+                Color::Yellow,
+            );
+        }
+
+        data.extend(track_data);
+
+        console.println_colored(&format!("OK ({:.2} MB)", size_mb), Color::Green);
+    }
+
+    console.println("");
+    console.status("Saving... ");
+    tokio::fs::write(&output_path, &data).await?;
+
+    let cue = build_cue_sheet(&album.title, artist_name, &file_name, tracks);
+    let cue_path = output_dir.join(format!("{}.cue", base_name));
+    tokio::fs::write(&cue_path, cue).await?;
+    console.println_colored("OK", Color::Green);
+
+    console.print("  Saved: ");
+    console.println_colored(&output_path.display().to_string(), Color::Cyan);
+    console.print("  Cue:   ");
+    console.println_colored(&cue_path.display().to_string(), Color::Cyan);
+    console.println_colored(
+        "  Note: raw FLAC concatenation plays only to the first track until re-muxed",
+        Color::Yellow,
+    );
+
+    Ok(())
+}
+
+async fn tag_file(
+    client: &mut TidalClient,
+    link: &str,
+    file: &Path,
+    console: &mut Console,
+) -> AppResult<()> {
+    let (content_type, id) = parse_tidal_link(link)?;
+    if content_type != "track" {
+        return Err(format!("tag expects a track link, got {}", content_type).into());
+    }
+
+    if !file.exists() {
+        return Err(format!("file not found: {}", file.display()).into());
+    }
+
+    let track_id: u64 = id.parse()?;
+    let track = client.get_track(track_id).await?;
+    let full_title = build_full_title(&track.title, track.version.as_deref());
+
+    console.println("");
+    console.println(&format!("Tagging: {}", track.display_title()));
+
+    console.status("Fetching stream info... ");
+    let stream_info = client
+        .get_stream_info(track.id, AudioQuality::HiResLossless)
+        .await?;
+    console.println_colored("OK", Color::Green);
+
+    // Make sure the local file's container matches what we'd tag it as.
+    let header = tokio::fs::read(file).await?;
+    let container = detect_container(&header);
+    let ext = file
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let matches_container = match container {
+        ContainerKind::Flac => ext == "flac",
+        ContainerKind::Mp4 => ext == "m4a" || ext == "mp4",
+    };
+    if !matches_container {
+        return Err(format!(
+            "container mismatch: {} does not look like a .{} file",
+            file.display(),
+            ext
+        )
+        .into());
+    }
+
+    // Same selection and normalization as a fresh download: synced lyrics
+    // first, TTML converted to LRC rather than embedded as raw XML.
+    let lyrics_content = match client.get_lyrics(track.id).await {
+        Ok(lyrics) => LyricsContent::from_response(lyrics).for_embedding(),
+        Err(_) => None,
+    };
+
+    console.status("Embedding metadata... ");
+    embed_metadata(
+        client,
+        file,
+        &track,
+        &full_title,
+        &stream_info,
         lyrics_content,
+        None,
     )
     .await?;
     console.println_colored("OK", Color::Green);
@@ -888,13 +1360,51 @@ async fn download_track(
     Ok(())
 }
 
+async fn download_video_cover(
+    album: &Album,
+    album_folder: &Path,
+    console: &mut Console,
+) -> AppResult<()> {
+    let Some(url) = album.video_cover_url(ImageSize::XLarge) else {
+        return Ok(());
+    };
+
+    console.status("Fetching video cover... ");
+    let resp = reqwest::get(&url).await?;
+    if !resp.status().is_success() {
+        console.println_colored("not available", Color::Yellow);
+        return Ok(());
+    }
+
+    let bytes = resp.bytes().await?;
+    let cover_path = album_folder.join("cover.mp4");
+    tokio::fs::write(&cover_path, &bytes).await?;
+    console.println_colored("OK", Color::Green);
+    console.print("  Saved: ");
+    console.println_colored(&cover_path.display().to_string(), Color::Cyan);
+
+    Ok(())
+}
+
 async fn download_album(
     client: &mut TidalClient,
     album_id: u64,
-    output_dir: &PathBuf,
+    output_dir: &Path,
+    opts: &DownloadOptions,
     console: &mut Console,
 ) -> AppResult<()> {
     let album = client.get_album(album_id).await?;
+
+    // Bail out before creating a folder of N per-track failures.
+    if !album.is_streamable() {
+        let reason = if album.pay_to_stream == Some(true) {
+            "this album requires purchase"
+        } else {
+            "this album is not available for streaming"
+        };
+        return Err(format!("Cannot download \"{}\": {}", album.title, reason).into());
+    }
+
     let artist_name = album
         .artist
         .as_ref()
@@ -913,13 +1423,57 @@ async fn download_album(
     )));
     tokio::fs::create_dir_all(&album_folder).await?;
 
-    let tracks_page = client.get_album_tracks(album_id, 100, 0).await?;
-    let total = tracks_page.items.len();
+    let mut tracks = client.get_all_album_tracks(album_id).await?;
+    sort_album_tracks(&mut tracks);
+    let total = tracks.len();
+
+    if opts.video_cover
+        && let Err(e) = download_video_cover(&album, &album_folder, console).await {
+            console.error(&format!("Failed to download video cover: {}", e));
+        }
+
+    let album_artist = resolve_album_artist(&album, &tracks, opts.various_artists);
+    let mut opts = opts.clone();
+    opts.album_artist = Some(album_artist);
+    let opts = &opts;
+
+    if opts.single_file {
+        return download_album_single_file(
+            client,
+            &album,
+            &artist_name,
+            &tracks,
+            &album_folder,
+            opts,
+            console,
+        )
+        .await;
+    }
 
-    for (i, track) in tracks_page.items.iter().enumerate() {
+    // Pad track numbers to the album's track-count width so files sort in
+    // players; multi-disc albums get a disc prefix like "1-01".
+    let width = album
+        .number_of_tracks
+        .unwrap_or(total as u32)
+        .to_string()
+        .len()
+        .max(2);
+    let multi_disc = album.number_of_volumes.unwrap_or(1) > 1;
+
+    for (i, track) in tracks.iter().enumerate() {
         console.println("");
         console.println(&format!("[{}/{}]", i + 1, total));
-        if let Err(e) = download_track(client, track, &album_folder, console).await {
+        // Position in the listing is the stable fallback when the API omits
+        // a track number.
+        let number = track.track_number.unwrap_or(i as u32 + 1);
+        let prefix = if multi_disc {
+            format!("{}-{:0width$}", track.volume_number.unwrap_or(1), number)
+        } else {
+            format!("{:0width$}", number)
+        };
+        let mut track_opts = opts.clone();
+        track_opts.track_prefix = Some(prefix);
+        if let Err(e) = download_track(client, track, &album_folder, &track_opts, console).await {
             console.error(&format!("Failed to download: {}", e));
         }
     }
@@ -932,10 +1486,267 @@ async fn download_album(
     Ok(())
 }
 
+/// Download a video by walking its HLS ladder: fetch the master playlist,
+/// pick the highest-bandwidth variant, then concatenate that variant's media
+/// segments. Tidal's video segments are MPEG-TS, so the output is a `.ts`
+/// file most players handle directly (remux with ffmpeg for `.mp4`).
+async fn download_video(
+    client: &mut TidalClient,
+    video_id: u64,
+    output_dir: &Path,
+    console: &mut Console,
+) -> AppResult<()> {
+    let video = client.get_video(video_id).await?;
+
+    console.println("");
+    console.println(&format!(
+        "Video: {} [{}]",
+        video.display_title(),
+        video.duration_formatted()
+    ));
+
+    console.status("Fetching stream info... ");
+    let stream_info = client
+        .get_video_stream_info(video_id, VideoQuality::High)
+        .await?;
+    let variant = stream_info
+        .best_variant()
+        .ok_or("No variant streams in video manifest")?;
+    console.println_colored(
+        &format!(
+            "OK ({})",
+            variant.resolution.as_deref().unwrap_or("unknown resolution")
+        ),
+        Color::Green,
+    );
+
+    let filename = format!("{}.ts", sanitize_filename(&video.display_title()));
+    let output_path = output_dir.join(&filename);
+    if output_path.exists() {
+        console.println_colored(
+            &format!("skipped (already exists: {})", output_path.display()),
+            Color::Yellow,
+        );
+        return Ok(());
+    }
+
+    console.status("Downloading... ");
+    let http = reqwest::Client::new();
+    let playlist = http.get(&variant.url).send().await?.text().await?;
+    // Media playlists may reference segments relative to their own URL.
+    let base = &variant.url[..variant.url.rfind('/').map_or(0, |i| i + 1)];
+    let segments: Vec<String> = playlist
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            if line.starts_with("http") {
+                line.to_string()
+            } else {
+                format!("{}{}", base, line)
+            }
+        })
+        .collect();
+    if segments.is_empty() {
+        return Err("No segments in video media playlist".into());
+    }
+
+    let mut data = Vec::new();
+    for segment in &segments {
+        let bytes = http.get(segment).send().await?.bytes().await?;
+        data.extend_from_slice(&bytes);
+    }
+    let size_mb = data.len() as f64 / (1024.0 * 1024.0);
+    console.println_colored(&format!("OK ({:.2} MB)", size_mb), Color::Green);
+
+    console.status("Saving... ");
+    tokio::fs::write(&output_path, &data).await?;
+    console.println_colored("OK", Color::Green);
+
+    console.print("  Saved: ");
+    console.println_colored(&output_path.display().to_string(), Color::Cyan);
+
+    Ok(())
+}
+
+/// Download every track of a mix into a folder named after the mix title.
+/// `get_mix_tracks` takes only a limit — the endpoint has no offset — so one
+/// request at the maximum page size of 100 covers it; Tidal mixes are
+/// curated to a few dozen tracks and never approach that cap.
+async fn download_mix(
+    client: &mut TidalClient,
+    mix_id: &str,
+    output_dir: &Path,
+    opts: &DownloadOptions,
+    console: &mut Console,
+) -> AppResult<()> {
+    let title = client
+        .get_mix_info(mix_id)
+        .await
+        .ok()
+        .and_then(|mix| mix.title)
+        .unwrap_or_else(|| format!("Mix {}", mix_id));
+
+    let page = client.get_mix_tracks(mix_id, 100).await?;
+
+    console.println("");
+    console.println("Mix Download");
+    console.println(&format!("Mix:    {}", title));
+    console.println(&format!("Tracks: {}", page.items.len()));
+
+    let mix_folder = output_dir.join(sanitize_filename(&title));
+    tokio::fs::create_dir_all(&mix_folder).await?;
+
+    for mix_item in &page.items {
+        download_track(client, &mix_item.item, &mix_folder, opts, console).await?;
+    }
+
+    Ok(())
+}
+
+/// What a numbered search result resolves to when the user picks it.
+enum SearchChoice {
+    Track(Box<Track>),
+    Album(u64),
+    Playlist(String),
+    Artist,
+}
+
+/// Run a combined search, print numbered results grouped by category, and
+/// download whichever entry the user picks. Artists are listed (they make
+/// good disambiguators) but can't be downloaded wholesale — picking one
+/// points the user at its albums instead.
+async fn run_search(
+    client: &mut TidalClient,
+    query: &str,
+    kind: Option<SearchKind>,
+    limit: u32,
+    output_dir: &Path,
+    opts: &DownloadOptions,
+    console: &mut Console,
+) -> AppResult<()> {
+    let results = client.search(query, limit).await?;
+    let mut choices: Vec<SearchChoice> = Vec::new();
+
+    let wants = |section: SearchKind| kind.is_none() || kind == Some(section);
+
+    if wants(SearchKind::Tracks)
+        && let Some(tracks) = results.tracks
+        && !tracks.items.is_empty()
+    {
+        console.println_colored("Tracks", Color::Cyan);
+        for track in tracks.items {
+            console.println(&format!(
+                "{:3}. {} [{}]",
+                choices.len() + 1,
+                track.display_title(),
+                format_duration(track.duration)
+            ));
+            choices.push(SearchChoice::Track(Box::new(track)));
+        }
+    }
+
+    if wants(SearchKind::Albums)
+        && let Some(albums) = results.albums
+        && !albums.items.is_empty()
+    {
+        console.println_colored("Albums", Color::Cyan);
+        for album in albums.items {
+            let artist = album
+                .artist
+                .as_ref()
+                .map(|a| a.name.clone())
+                .or_else(|| {
+                    album
+                        .artists
+                        .as_ref()
+                        .and_then(|a| a.first())
+                        .map(|a| a.name.clone())
+                })
+                .unwrap_or_else(|| "Unknown Artist".to_string());
+            console.println(&format!(
+                "{:3}. {} - {} ({} tracks)",
+                choices.len() + 1,
+                artist,
+                album.title,
+                album.number_of_tracks.unwrap_or(0)
+            ));
+            choices.push(SearchChoice::Album(album.id));
+        }
+    }
+
+    if wants(SearchKind::Artists)
+        && let Some(artists) = results.artists
+        && !artists.items.is_empty()
+    {
+        console.println_colored("Artists", Color::Cyan);
+        for artist in artists.items {
+            console.println(&format!("{:3}. {}", choices.len() + 1, artist.name));
+            choices.push(SearchChoice::Artist);
+        }
+    }
+
+    if wants(SearchKind::Playlists)
+        && let Some(playlists) = results.playlists
+        && !playlists.items.is_empty()
+    {
+        console.println_colored("Playlists", Color::Cyan);
+        for playlist in playlists.items {
+            console.println(&format!(
+                "{:3}. {} ({} tracks)",
+                choices.len() + 1,
+                playlist.title,
+                playlist.number_of_tracks.unwrap_or(0)
+            ));
+            choices.push(SearchChoice::Playlist(playlist.uuid));
+        }
+    }
+
+    if choices.is_empty() {
+        console.info("No results.");
+        return Ok(());
+    }
+
+    console.println("");
+    console.print(&format!(
+        "Download which result? (1-{}, Enter to cancel): ",
+        choices.len()
+    ));
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(());
+    }
+    let index: usize = input.parse().map_err(|_| format!("Not a number: {}", input))?;
+    if index == 0 || index > choices.len() {
+        return Err(format!("Pick a number between 1 and {}", choices.len()).into());
+    }
+
+    match choices.swap_remove(index - 1) {
+        SearchChoice::Track(track) => {
+            download_track(client, &track, output_dir, opts, console).await?;
+        }
+        SearchChoice::Album(album_id) => {
+            download_album(client, album_id, output_dir, opts, console).await?;
+        }
+        SearchChoice::Playlist(uuid) => {
+            let playlist = client.get_playlist(&uuid).await?;
+            download_playlist(client, &playlist, output_dir, opts, console).await?;
+        }
+        SearchChoice::Artist => {
+            return Err("Whole-artist downloads aren't supported; pick one of the artist's albums instead".into());
+        }
+    }
+
+    Ok(())
+}
+
 async fn download_playlist(
     client: &mut TidalClient,
     playlist: &Playlist,
-    output_dir: &PathBuf,
+    output_dir: &Path,
+    opts: &DownloadOptions,
     console: &mut Console,
 ) -> AppResult<()> {
     let creator_name = playlist
@@ -974,7 +1785,7 @@ async fn download_playlist(
             console.println("");
             console.println(&format!("[{}/{}]", track_num, total));
             if let Err(e) =
-                download_track(client, &playlist_item.item, &playlist_folder, console).await
+                download_track(client, &playlist_item.item, &playlist_folder, opts, console).await
             {
                 console.error(&format!("Failed to download: {}", e));
             }
@@ -994,34 +1805,495 @@ async fn download_playlist(
     Ok(())
 }
 
+/// Export the whole library to `out` as JSON Lines: one object per favorite
+/// track/album/artist/playlist and per playlist folder, each tagged with a
+/// `type` field and the date it was added. Each page is written to disk as it
+/// arrives, so memory stays flat no matter how large the library is.
+async fn export_library(
+    client: &mut TidalClient,
+    out: &Path,
+    console: &mut Console,
+) -> AppResult<()> {
+    const PAGE_SIZE: u32 = 50;
+
+    let session = client.get_session().await?;
+    let user_id = session.user_id;
+
+    let file = std::fs::File::create(out)?;
+    let mut writer = std::io::BufWriter::new(file);
+    let mut total = 0usize;
+
+    /// One exported line. Borrows the item so pages serialize without copies.
+    #[derive(Serialize)]
+    struct ExportRecord<'a, T> {
+        #[serde(rename = "type")]
+        kind: &'static str,
+        added: Option<&'a str>,
+        item: &'a T,
+    }
+
+    fn write_record<T: Serialize>(
+        writer: &mut impl Write,
+        kind: &'static str,
+        added: Option<&str>,
+        item: &T,
+    ) -> AppResult<()> {
+        let record = ExportRecord { kind, added, item };
+        serde_json::to_writer(&mut *writer, &record)?;
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    // The four favorite listings share a shape but not a type, so each gets
+    // its own paging loop; all stream straight to the writer.
+    macro_rules! export_favorites {
+        ($label:expr, $kind:expr, $method:ident) => {{
+            console.status(concat!("Exporting ", $label, "... "));
+            let mut offset = 0u32;
+            let mut count = 0usize;
+            loop {
+                let page = client
+                    .$method(
+                        user_id,
+                        PAGE_SIZE,
+                        offset,
+                        FavoriteOrder::Date,
+                        OrderDirection::Asc,
+                    )
+                    .await?;
+                let fetched = page.items.len();
+                for favorite in &page.items {
+                    write_record(
+                        &mut writer,
+                        $kind,
+                        favorite.created.as_deref(),
+                        &favorite.item,
+                    )?;
+                }
+                count += fetched;
+                offset += fetched as u32;
+                if fetched < PAGE_SIZE as usize {
+                    break;
+                }
+            }
+            total += count;
+            console.println_colored(&format!("OK ({})", count), Color::Green);
+        }};
+    }
+
+    export_favorites!("favorite tracks", "track", get_favorite_tracks);
+    export_favorites!("favorite albums", "album", get_favorite_albums);
+    export_favorites!("favorite artists", "artist", get_favorite_artists);
+    export_favorites!("favorite playlists", "playlist", get_favorite_playlists);
+
+    console.status("Exporting playlist folders... ");
+    let folders = client.get_folders(user_id).await?;
+    let folder_count = folders.len();
+    for folder in &folders {
+        write_record(&mut writer, "folder", None, folder)?;
+    }
+    total += folder_count;
+    console.println_colored(&format!("OK ({})", folder_count), Color::Green);
+
+    /// An owned playlist additionally carries its track listing so `import`
+    /// can recreate it with contents instead of as an empty shell.
+    #[derive(Serialize)]
+    struct PlaylistExportRecord<'a> {
+        #[serde(rename = "type")]
+        kind: &'static str,
+        added: Option<&'a str>,
+        item: &'a Playlist,
+        tracks: &'a [serde_json::Value],
+    }
+
+    // Owned playlists live under `users/{id}/playlists`, not the favorites
+    // listing, so they need their own pass.
+    console.status("Exporting owned playlists... ");
+    let playlists = client.get_all_user_playlists(user_id).await?;
+    let playlist_count = playlists.len();
+    for playlist in &playlists {
+        let mut tracks = Vec::new();
+        let mut offset = 0u32;
+        loop {
+            let page = client
+                .get_playlist_entries(&playlist.uuid, PAGE_SIZE, offset)
+                .await?;
+            let fetched = page.items.len();
+            for entry in page.items {
+                // Video entries can't be re-added through the track
+                // endpoint, so only tracks make it into the listing. The
+                // ISRC rides along for id re-resolution on import.
+                if let PlaylistEntryItem::Track(track) = entry.item {
+                    tracks.push(serde_json::json!({ "id": track.id, "isrc": track.isrc }));
+                }
+            }
+            offset += fetched as u32;
+            if fetched < PAGE_SIZE as usize {
+                break;
+            }
+        }
+
+        let record = PlaylistExportRecord {
+            kind: "playlist",
+            added: playlist.created.as_deref(),
+            item: playlist,
+            tracks: &tracks,
+        };
+        serde_json::to_writer(&mut writer, &record)?;
+        writeln!(writer)?;
+    }
+    total += playlist_count;
+    console.println_colored(&format!("OK ({})", playlist_count), Color::Green);
+
+    writer.flush()?;
+
+    console.print("  Exported ");
+    console.print_colored(&total.to_string(), Color::Cyan);
+    console.print(" records to ");
+    console.println_colored(&out.display().to_string(), Color::Cyan);
+
+    Ok(())
+}
+
+/// Tally of one `import` run, reported to the user at the end.
+#[derive(Debug, Default)]
+struct ImportResult {
+    added: usize,
+    /// Records already present on the account (import is idempotent).
+    skipped: usize,
+    failed: Vec<String>,
+}
+
+/// Resolve a track that no longer exists under its exported id by searching
+/// for its ISRC; Tidal's search indexes the code directly. Only an exact
+/// ISRC match counts — a fuzzy hit would silently import the wrong track.
+async fn resolve_track_by_isrc(client: &mut TidalClient, isrc: &str) -> Option<u64> {
+    let matches = client.get_track_by_isrc(isrc).await.ok()?;
+    matches.first().map(|track| track.id)
+}
+
+/// Recreate the favorites and playlists from an `export` file on the current
+/// account. Existing favorites and playlists with a matching title are
+/// skipped, so re-running an import never duplicates. Tracks whose exported
+/// id has been retired are re-resolved by ISRC before being given up on.
+async fn import_library(
+    client: &mut TidalClient,
+    file: &Path,
+    console: &mut Console,
+) -> AppResult<()> {
+    use std::collections::{
+        HashMap,
+        HashSet,
+    };
+    use std::io::BufRead;
+
+    let session = client.get_session().await?;
+    let user_id = session.user_id;
+
+    console.status("Reading current library... ");
+    let favorite_ids = client.get_favorite_ids(user_id).await?;
+    let have_tracks: HashSet<u64> = favorite_ids.tracks.unwrap_or_default().into_iter().collect();
+    let have_albums: HashSet<u64> = favorite_ids.albums.unwrap_or_default().into_iter().collect();
+    let have_artists: HashSet<u64> =
+        favorite_ids.artists.unwrap_or_default().into_iter().collect();
+    let have_playlists: HashSet<String> =
+        favorite_ids.playlists.unwrap_or_default().into_iter().collect();
+    let own_playlists: HashMap<String, String> = client
+        .get_all_user_playlists(user_id)
+        .await?
+        .into_iter()
+        .map(|p| (p.title, p.uuid))
+        .collect();
+    console.println_colored("OK", Color::Green);
+
+    let mut result = ImportResult::default();
+    let reader = std::io::BufReader::new(std::fs::File::open(file)?);
+
+    for (line_number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: serde_json::Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(e) => {
+                result.failed.push(format!("line {}: {}", line_number + 1, e));
+                continue;
+            }
+        };
+        let item = &record["item"];
+
+        match record["type"].as_str().unwrap_or_default() {
+            "track" => {
+                let Some(id) = item["id"].as_u64() else {
+                    result.failed.push(format!("line {}: track without id", line_number + 1));
+                    continue;
+                };
+                if have_tracks.contains(&id) {
+                    result.skipped += 1;
+                    continue;
+                }
+                match client.add_favorite_track(user_id, id).await {
+                    Ok(()) => result.added += 1,
+                    Err(e) => {
+                        // Exported id may be retired; try the ISRC instead.
+                        let resolved = match item["isrc"].as_str() {
+                            Some(isrc) => resolve_track_by_isrc(client, isrc).await,
+                            None => None,
+                        };
+                        match resolved {
+                            Some(new_id)
+                                if client.add_favorite_track(user_id, new_id).await.is_ok() =>
+                            {
+                                result.added += 1;
+                            }
+                            _ => result.failed.push(format!("track {}: {}", id, e)),
+                        }
+                    }
+                }
+            }
+            "album" => {
+                let Some(id) = item["id"].as_u64() else {
+                    result.failed.push(format!("line {}: album without id", line_number + 1));
+                    continue;
+                };
+                if have_albums.contains(&id) {
+                    result.skipped += 1;
+                } else {
+                    match client.add_favorite_album(user_id, id).await {
+                        Ok(()) => result.added += 1,
+                        Err(e) => result.failed.push(format!("album {}: {}", id, e)),
+                    }
+                }
+            }
+            "artist" => {
+                let Some(id) = item["id"].as_u64() else {
+                    result.failed.push(format!("line {}: artist without id", line_number + 1));
+                    continue;
+                };
+                if have_artists.contains(&id) {
+                    result.skipped += 1;
+                } else {
+                    match client.add_favorite_artist(user_id, id).await {
+                        Ok(()) => result.added += 1,
+                        Err(e) => result.failed.push(format!("artist {}: {}", id, e)),
+                    }
+                }
+            }
+            "playlist" => {
+                let title = item["title"].as_str().unwrap_or_default();
+                let uuid = item["uuid"].as_str().unwrap_or_default();
+                let owned = item["creator"]["id"].as_u64() == Some(user_id);
+                if owned {
+                    if own_playlists.contains_key(title) {
+                        result.skipped += 1;
+                        continue;
+                    }
+                    let description = item["description"].as_str().unwrap_or_default();
+                    match client.create_playlist(user_id, title, description).await {
+                        Ok(created) => {
+                            result.added += 1;
+                            // The track listing is restored in one batched
+                            // add when every entry still has a usable id;
+                            // otherwise track by track, re-resolving retired
+                            // ids by ISRC. Entries are `{id, isrc}` objects
+                            // (older exports wrote bare ids).
+                            if let Some(tracks) = record["tracks"].as_array()
+                                && !tracks.is_empty()
+                            {
+                                let ids: Vec<u64> = tracks
+                                    .iter()
+                                    .filter_map(|t| t.as_u64().or_else(|| t["id"].as_u64()))
+                                    .collect();
+                                let batched = ids.len() == tracks.len()
+                                    && client
+                                        .add_tracks_to_playlist(&created.uuid, &ids)
+                                        .await
+                                        .is_ok();
+                                if !batched {
+                                    let mut restored = 0usize;
+                                    for entry in tracks {
+                                        let id =
+                                            entry.as_u64().or_else(|| entry["id"].as_u64());
+                                        if let Some(id) = id
+                                            && client
+                                                .add_tracks_to_playlist(&created.uuid, &[id])
+                                                .await
+                                                .is_ok()
+                                        {
+                                            restored += 1;
+                                            continue;
+                                        }
+                                        let resolved = match entry["isrc"].as_str() {
+                                            Some(isrc) => {
+                                                resolve_track_by_isrc(client, isrc).await
+                                            }
+                                            None => None,
+                                        };
+                                        if let Some(new_id) = resolved
+                                            && client
+                                                .add_tracks_to_playlist(&created.uuid, &[new_id])
+                                                .await
+                                                .is_ok()
+                                        {
+                                            restored += 1;
+                                        }
+                                    }
+                                    if restored < tracks.len() {
+                                        result.failed.push(format!(
+                                            "playlist \"{}\": restored {} of {} tracks",
+                                            title,
+                                            restored,
+                                            tracks.len()
+                                        ));
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            result.failed.push(format!("playlist \"{}\": {}", title, e));
+                        }
+                    }
+                } else if have_playlists.contains(uuid) {
+                    result.skipped += 1;
+                } else {
+                    match client.add_favorite_playlist(user_id, uuid).await {
+                        Ok(()) => result.added += 1,
+                        Err(e) => result.failed.push(format!("playlist \"{}\": {}", title, e)),
+                    }
+                }
+            }
+            // Folder creation has no public endpoint; folders only inform
+            // the export's structure.
+            "folder" => result.skipped += 1,
+            other => {
+                result
+                    .failed
+                    .push(format!("line {}: unknown record type \"{}\"", line_number + 1, other));
+            }
+        }
+    }
+
+    console.print("  Imported ");
+    console.print_colored(&result.added.to_string(), Color::Green);
+    console.print(", already present ");
+    console.print_colored(&result.skipped.to_string(), Color::Cyan);
+    console.print(", failed ");
+    console.println_colored(&result.failed.len().to_string(), Color::Red);
+    for failure in &result.failed {
+        console.println_colored(&format!("  {}", failure), Color::Yellow);
+    }
+
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> AppResult<()> {
     let args = Args::parse();
     let mut console = Console::new();
 
-    let (content_type, id) = parse_tidal_link(&args.link)?;
-
     console.println("");
     console.println("tidal-dl - Tidal Music Downloader");
 
+    if let Some(Command::Tag { link, file }) = &args.command {
+        let mut client = get_client(&mut console).await?;
+        tag_file(&mut client, link, file, &mut console).await?;
+        console.println("");
+        console.success("Done.");
+        return Ok(());
+    }
+
+    if let Some(Command::Export { out }) = &args.command {
+        let mut client = get_client(&mut console).await?;
+        export_library(&mut client, out, &mut console).await?;
+        console.println("");
+        console.success("Done.");
+        return Ok(());
+    }
+
+    if let Some(Command::Import { file }) = &args.command {
+        let mut client = get_client(&mut console).await?;
+        import_library(&mut client, file, &mut console).await?;
+        console.println("");
+        console.success("Done.");
+        return Ok(());
+    }
+
+    if let Some(Command::Search { query, kind, limit }) = &args.command {
+        let mut client = get_client(&mut console).await?;
+        let output_dir = args
+            .output
+            .clone()
+            .unwrap_or_else(|| std::env::current_dir().unwrap());
+        let opts = DownloadOptions {
+            quality: args.quality.clone(),
+            lossless_only: args.lossless_only,
+            single_file: args.single_file,
+            credits_sidecar: args.credits_sidecar,
+            various_artists: args.various_artists,
+            video_cover: args.video_cover,
+            lrc_encoding: args.lrc_encoding,
+            also_quality: args.also_quality,
+            template: args.template.clone(),
+            album_artist: None,
+            track_prefix: None,
+        };
+        run_search(
+            &mut client,
+            query,
+            *kind,
+            *limit,
+            &output_dir,
+            &opts,
+            &mut console,
+        )
+        .await?;
+        console.println("");
+        console.success("Done.");
+        return Ok(());
+    }
+
+    let link = args.link.as_deref().ok_or("No link provided")?;
+    let (content_type, id) = parse_tidal_link(link)?;
+
     let mut client = get_client(&mut console).await?;
     let output_dir = args
         .output
         .unwrap_or_else(|| std::env::current_dir().unwrap());
+    let opts = DownloadOptions {
+        quality: args.quality,
+        lossless_only: args.lossless_only,
+        single_file: args.single_file,
+        credits_sidecar: args.credits_sidecar,
+        various_artists: args.various_artists,
+        video_cover: args.video_cover,
+        lrc_encoding: args.lrc_encoding,
+        also_quality: args.also_quality,
+        template: args.template.clone(),
+        album_artist: None,
+        track_prefix: None,
+    };
 
     match content_type.as_str() {
         "track" => {
             let track_id: u64 = id.parse()?;
             let track = client.get_track(track_id).await?;
-            download_track(&mut client, &track, &output_dir, &mut console).await?;
+            download_track(&mut client, &track, &output_dir, &opts, &mut console).await?;
         }
         "album" => {
             let album_id: u64 = id.parse()?;
-            download_album(&mut client, album_id, &output_dir, &mut console).await?;
+            download_album(&mut client, album_id, &output_dir, &opts, &mut console).await?;
         }
         "playlist" => {
             let playlist = client.get_playlist(&id).await?;
-            download_playlist(&mut client, &playlist, &output_dir, &mut console).await?;
+            download_playlist(&mut client, &playlist, &output_dir, &opts, &mut console).await?;
+        }
+        "video" => {
+            let video_id: u64 = id.parse()?;
+            download_video(&mut client, video_id, &output_dir, &mut console).await?;
+        }
+        "mix" => {
+            download_mix(&mut client, &id, &output_dir, &opts, &mut console).await?;
         }
         _ => {
             return Err(format!("Unsupported content type: {}", content_type).into());
@@ -1033,3 +2305,111 @@ async fn main() -> AppResult<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tidal_link_covers_all_content_types() {
+        for (link, kind, id) in [
+            ("https://tidal.com/track/123", "track", "123"),
+            ("https://listen.tidal.com/album/456", "album", "456"),
+            (
+                "https://tidal.com/browse/playlist/0fe0d248-5f5f-4e0a-9f9e-87a1a1d4a6ed",
+                "playlist",
+                "0fe0d248-5f5f-4e0a-9f9e-87a1a1d4a6ed",
+            ),
+            ("https://tidal.com/video/789", "video", "789"),
+            ("https://listen.tidal.com/video/789", "video", "789"),
+            ("https://tidal.com/browse/video/789", "video", "789"),
+            (
+                "https://tidal.com/mix/000ec0b1b4b5e4cd5c057e2",
+                "mix",
+                "000ec0b1b4b5e4cd5c057e2",
+            ),
+            (
+                "https://listen.tidal.com/mix/000ec0b1b4b5e4cd5c057e2",
+                "mix",
+                "000ec0b1b4b5e4cd5c057e2",
+            ),
+            (
+                "https://tidal.com/browse/mix/000ec0b1b4b5e4cd5c057e2",
+                "mix",
+                "000ec0b1b4b5e4cd5c057e2",
+            ),
+        ] {
+            let (kind_got, id_got) = parse_tidal_link(link).unwrap();
+            assert_eq!((kind_got.as_str(), id_got.as_str()), (kind, id), "{}", link);
+        }
+
+        assert!(parse_tidal_link("https://example.com/video/789").is_err());
+    }
+
+    fn track(id: u64, volume: u32, number: u32) -> Track {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": format!("Track {}", id),
+            "duration": 200,
+            "explicit": false,
+            "artists": [],
+            "trackNumber": number,
+            "volumeNumber": volume,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn format_template_substitutes_and_sanitizes() {
+        let track: Track = serde_json::from_value(serde_json::json!({
+            "id": 9,
+            "title": "Back In Black",
+            "duration": 255,
+            "explicit": false,
+            "artists": [{"id": 1, "name": "AC/DC"}],
+            "trackNumber": 1,
+            "isrc": "AUAP08000001",
+            "album": {"id": 2, "title": "Back In Black", "releaseDate": "1980-07-25"},
+        }))
+        .unwrap();
+
+        assert_eq!(
+            format_template(
+                "{track_number} - {title}",
+                &track,
+                "Back In Black",
+                "LOSSLESS"
+            ),
+            "01 - Back In Black"
+        );
+        assert_eq!(
+            format_template(
+                "{artist}/{year}/{isrc} [{quality}]",
+                &track,
+                "Back In Black",
+                "LOSSLESS"
+            ),
+            // The artist's slash is sanitized; the template's own slashes stay.
+            "AC_DC/1980/AUAP08000001 [LOSSLESS]"
+        );
+        // Unknown placeholders stay literal so typos are visible.
+        assert_eq!(
+            format_template("{titel}", &track, "Back In Black", "LOW"),
+            "{titel}"
+        );
+    }
+
+    #[test]
+    fn sort_album_tracks_orders_by_disc_then_track() {
+        let mut tracks = vec![
+            track(1, 2, 1),
+            track(2, 1, 3),
+            track(3, 1, 1),
+            track(4, 2, 2),
+            track(5, 1, 2),
+        ];
+        sort_album_tracks(&mut tracks);
+        let ids: Vec<u64> = tracks.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![3, 5, 2, 1, 4]);
+    }
+}
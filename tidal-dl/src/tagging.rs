@@ -0,0 +1,475 @@
+use std::path::Path;
+
+use lofty::config::WriteOptions;
+use lofty::mp4::{
+    Atom,
+    AtomData,
+    AtomIdent,
+    Ilst,
+};
+use lofty::picture::{
+    MimeType,
+    Picture,
+    PictureType,
+};
+use lofty::prelude::*;
+use lofty::probe::Probe;
+use lofty::tag::{
+    ItemKey,
+    ItemValue,
+    Tag,
+    TagItem,
+    TagType,
+};
+use tidal::{
+    Album,
+    Credit,
+    Track,
+};
+
+use crate::AppResult;
+
+/// A Tidal credit type normalized to the tag field it belongs in, so role
+/// handling is one testable mapping instead of string matches scattered
+/// through the embed path. Unrecognized types keep their original label in
+/// [`CreditRole::Other`] and end up in the comment field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CreditRole {
+    Producer,
+    MixEngineer,
+    Engineer,
+    Writer,
+    Composer,
+    Lyricist,
+    Arranger,
+    Conductor,
+    Remixer,
+    Performer,
+    Label,
+    Other(String),
+}
+
+impl CreditRole {
+    /// Classify one of Tidal's free-form credit type strings, case
+    /// insensitively and accepting the plural/synonym spellings the catalog
+    /// actually uses.
+    pub fn from_tidal_type(credit_type: &str) -> Self {
+        match credit_type.to_lowercase().as_str() {
+            "producer" | "producers" => CreditRole::Producer,
+            "mixer" | "mixing" | "mix engineer" => CreditRole::MixEngineer,
+            "engineer" | "recording engineer" | "audio engineer" => CreditRole::Engineer,
+            "writer" | "songwriter" => CreditRole::Writer,
+            "composer" | "composers" => CreditRole::Composer,
+            "lyricist" => CreditRole::Lyricist,
+            "arranger" => CreditRole::Arranger,
+            "conductor" => CreditRole::Conductor,
+            "remixer" | "remix" => CreditRole::Remixer,
+            "performer" | "performers" => CreditRole::Performer,
+            "record label" => CreditRole::Label,
+            _ => CreditRole::Other(credit_type.to_string()),
+        }
+    }
+
+    /// The lofty [`ItemKey`] this role writes to. `Performer` and `Other`
+    /// return `None`: they have no standard tag field and are appended to
+    /// the comment instead.
+    pub fn to_item_key(&self) -> Option<ItemKey> {
+        Some(match self {
+            CreditRole::Producer => ItemKey::Producer,
+            CreditRole::MixEngineer => ItemKey::MixEngineer,
+            CreditRole::Engineer => ItemKey::Engineer,
+            CreditRole::Writer => ItemKey::Writer,
+            CreditRole::Composer => ItemKey::Composer,
+            CreditRole::Lyricist => ItemKey::Lyricist,
+            CreditRole::Arranger => ItemKey::Arranger,
+            CreditRole::Conductor => ItemKey::Conductor,
+            CreditRole::Remixer => ItemKey::Remixer,
+            CreditRole::Label => ItemKey::Label,
+            CreditRole::Performer | CreditRole::Other(_) => return None,
+        })
+    }
+}
+
+/// Append to the comment tag with the ` | ` separator used throughout,
+/// creating it when absent.
+fn append_comment(tag: &mut Tag, text: &str) {
+    match tag.get_string(&ItemKey::Comment) {
+        Some(existing) => {
+            let merged = format!("{} | {}", existing, text);
+            tag.insert_text(ItemKey::Comment, merged);
+        }
+        None => {
+            tag.insert_text(ItemKey::Comment, text.to_string());
+        }
+    }
+}
+
+/// Everything needed to tag one audio file, assembled up front so writers
+/// don't have to touch the network themselves.
+#[derive(Clone)]
+pub struct TrackMetadata {
+    pub track: Track,
+    pub full_title: String,
+    /// The fully fetched album, when available; richer than `track.album`.
+    pub album: Option<Album>,
+    /// Overrides the album-artist fallback chain when set.
+    pub album_artist: Option<String>,
+    pub lyrics: Option<String>,
+    pub cover: Option<(Vec<u8>, MimeType)>,
+    /// Album-header credits, as returned by the album page.
+    pub credits: Vec<Credit>,
+    /// Pre-rendered encoder/quality description for the EncoderSettings tag.
+    pub encoder_settings: Option<String>,
+}
+
+/// A pluggable tag backend. The default implementation uses lofty, but the
+/// metadata-mapping logic is reusable by anything that can write tags.
+pub trait TagWriter {
+    fn write(&self, path: &Path, metadata: &TrackMetadata) -> AppResult<()>;
+}
+
+pub struct LoftyTagWriter;
+
+impl TagWriter for LoftyTagWriter {
+    fn write(&self, path: &Path, metadata: &TrackMetadata) -> AppResult<()> {
+        let track = &metadata.track;
+
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        let tag_type = if ext == "flac" {
+            TagType::VorbisComments
+        } else {
+            TagType::Mp4Ilst
+        };
+
+        let mut tagged_file = Probe::open(path)?.read()?;
+        if tagged_file.tag(tag_type).is_none() {
+            tagged_file.insert_tag(Tag::new(tag_type));
+        }
+
+        let tag = tagged_file
+            .tag_mut(tag_type)
+            .ok_or_else(|| "Failed to get tag".to_string())?;
+
+        let artists_joined = track
+            .artists
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        tag.set_title(metadata.full_title.clone());
+        tag.set_artist(artists_joined.clone());
+
+        if let Some(version) = track.version.as_ref() {
+            tag.insert_text(ItemKey::TrackSubtitle, version.clone());
+        }
+
+        if let Some(album_artist) = metadata.album_artist.as_deref() {
+            tag.insert_text(ItemKey::AlbumArtist, album_artist.to_string());
+        } else if let Some(album_artist) = track.album.as_ref().and_then(|a| a.primary_artist()) {
+            tag.insert_text(ItemKey::AlbumArtist, album_artist.name.clone());
+        } else if let Some(primary) = track.primary_artist() {
+            tag.insert_text(ItemKey::AlbumArtist, primary.name.clone());
+        } else {
+            tag.insert_text(ItemKey::AlbumArtist, artists_joined.clone());
+        }
+
+        tag.insert_text(ItemKey::Performer, artists_joined.clone());
+        tag.insert_text(ItemKey::OriginalArtist, artists_joined.clone());
+
+        if let Some(primary) = track.primary_artist() {
+            tag.insert_text(ItemKey::Composer, primary.name.clone());
+        } else {
+            tag.insert_text(ItemKey::Composer, artists_joined.clone());
+        }
+
+        for artist in &track.artists {
+            tag.push(TagItem::new(
+                ItemKey::TrackArtists,
+                ItemValue::Text(artist.name.clone()),
+            ));
+        }
+
+        if let Some(tags) = track
+            .media_metadata
+            .as_ref()
+            .and_then(|m| m.tags.as_ref())
+            .filter(|v| !v.is_empty())
+            .or_else(|| {
+                track
+                    .album
+                    .as_ref()
+                    .and_then(|a| a.media_metadata.as_ref())
+                    .and_then(|m| m.tags.as_ref())
+                    .filter(|v| !v.is_empty())
+            })
+        {
+            let genres = tags.join(", ");
+            tag.insert_text(ItemKey::Genre, genres);
+        }
+
+        let date_to_use = track
+            .album
+            .as_ref()
+            .and_then(|a| a.release_date.as_ref().or(a.stream_start_date.as_ref()))
+            .or(track.stream_start_date.as_ref());
+
+        if let Some(date) = date_to_use
+            && let Some(year_str) = date.split('-').next()
+                && let Ok(y) = year_str.parse::<u32>() {
+                    tag.set_year(y);
+                    tag.insert_text(ItemKey::Year, year_str.to_string());
+
+                    let date_only = date.split('T').next().unwrap_or(date);
+                    tag.insert_text(ItemKey::RecordingDate, date_only.to_string());
+                    tag.insert_text(ItemKey::ReleaseDate, date_only.to_string());
+                    tag.insert_text(ItemKey::OriginalReleaseDate, date_only.to_string());
+                }
+
+        if let Some(album) = &track.album {
+            tag.set_album(album.title.clone());
+
+            let full_album = metadata.album.as_ref().unwrap_or(album);
+            if let Some(total) = full_album.number_of_tracks {
+                tag.set_track_total(total);
+            }
+            if let Some(vol_total) = full_album.number_of_volumes {
+                tag.set_disk_total(vol_total);
+            }
+
+            if let Some(upc) = album.upc.clone() {
+                tag.insert_text(ItemKey::CatalogNumber, upc.clone());
+                tag.insert_text(ItemKey::Barcode, upc);
+            }
+
+            if let Some(album_type) = album.album_type.as_ref() {
+                tag.insert_text(ItemKey::OriginalMediaType, album_type.clone());
+            }
+        }
+
+        if let Some(n) = track.track_number {
+            tag.set_track(n);
+        }
+
+        if let Some(disc) = track.volume_number {
+            tag.set_disk(disc);
+        }
+
+        if let Some(isrc) = track.isrc.clone() {
+            tag.insert_text(ItemKey::Isrc, isrc);
+        }
+
+        if let Some(url) = track.url.as_ref() {
+            tag.insert_text(ItemKey::AudioSourceUrl, url.clone());
+        }
+
+        if track.explicit {
+            tag.insert_text(ItemKey::ParentalAdvisory, "Explicit".to_string());
+        }
+
+        if let Some(gain) = track.replay_gain {
+            tag.insert_text(ItemKey::ReplayGainTrackGain, format!("{gain:.2} dB"));
+        }
+
+        if let Some(peak) = track.peak {
+            tag.insert_text(ItemKey::ReplayGainTrackPeak, format!("{peak:.6}"));
+        }
+
+        if let Some(settings) = metadata.encoder_settings.as_ref() {
+            tag.insert_text(ItemKey::EncoderSettings, settings.clone());
+        }
+
+        tag.insert_text(ItemKey::EncoderSoftware, "Tidal".to_string());
+
+        if let Some(media_tags) = track
+            .media_metadata
+            .as_ref()
+            .and_then(|m| m.tags.as_ref())
+            .filter(|t| !t.is_empty())
+        {
+            let tags_str = media_tags.join(", ");
+            tag.insert_text(ItemKey::Description, format!("Quality: {}", tags_str));
+        }
+
+        if let Some(popularity) = track.popularity {
+            tag.insert_text(ItemKey::Popularimeter, popularity.to_string());
+        }
+
+        if let Some(c) = track
+            .copyright
+            .clone()
+            .or_else(|| track.album.as_ref().and_then(|a| a.copyright.clone()))
+        {
+            tag.insert_text(ItemKey::CopyrightMessage, c);
+        }
+
+        if let Some(album) = &track.album
+            && let Some(label_artist) = album.artist.as_ref() {
+                tag.insert_text(ItemKey::Label, label_artist.name.clone());
+                tag.insert_text(ItemKey::Publisher, label_artist.name.clone());
+            }
+
+        tag.insert_text(ItemKey::EncodedBy, "Tidal".to_string());
+
+        if let Some(key) = track.musical_key_formatted() {
+            tag.insert_text(ItemKey::InitialKey, key);
+        }
+
+        if let Some(bpm) = track.bpm {
+            tag.insert_text(ItemKey::Bpm, bpm.to_string());
+            tag.insert_text(ItemKey::IntegerBpm, bpm.to_string());
+        }
+
+        let mut comment_parts = Vec::new();
+
+        if let Some(popularity) = track.popularity {
+            comment_parts.push(format!("Popularity: {}/100", popularity));
+        }
+
+        if track.stream_ready == Some(true)
+            && let Some(start_date) = track.stream_start_date.as_ref()
+                && let Some(date_only) = start_date.split('T').next() {
+                    comment_parts.push(format!("Available since: {}", date_only));
+                }
+
+        comment_parts.push(format!("Tidal ID: {}", track.id));
+
+        if !comment_parts.is_empty() {
+            let comment = comment_parts.join(" | ");
+            if let Some(existing) = tag.get_string(&ItemKey::Comment) {
+                tag.insert_text(ItemKey::Comment, format!("{} | {}", existing, comment));
+            } else {
+                tag.insert_text(ItemKey::Comment, comment);
+            }
+        }
+
+        if let Some(text) = metadata.lyrics.clone() {
+            tag.insert_text(ItemKey::Lyrics, text);
+        }
+
+        for credit in metadata.credits.iter() {
+            let contributors = credit
+                .contributors
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            if contributors.is_empty() {
+                continue;
+            }
+
+            match CreditRole::from_tidal_type(&credit.credit_type) {
+                // Composer may already be set from the track artists; the
+                // credit is only a fallback.
+                CreditRole::Composer => {
+                    if tag.get_string(&ItemKey::Composer).is_none() {
+                        tag.insert_text(ItemKey::Composer, contributors);
+                    }
+                }
+                CreditRole::Label => {
+                    tag.insert_text(ItemKey::Label, contributors.clone());
+                    tag.insert_text(ItemKey::Publisher, contributors);
+                }
+                CreditRole::Performer => {
+                    append_comment(tag, &format!("Performers: {}", contributors));
+                }
+                CreditRole::Other(original) => {
+                    append_comment(tag, &format!("{}: {}", original, contributors));
+                }
+                role => {
+                    let key = role.to_item_key().expect("mapped roles carry an ItemKey");
+                    tag.insert_text(key, contributors);
+                }
+            }
+        }
+
+        if let Some((cover_bytes, mime)) = metadata.cover.clone() {
+            let picture =
+                Picture::new_unchecked(PictureType::CoverFront, Some(mime), None, cover_bytes);
+            tag.push_picture(picture);
+        }
+
+        if tag_type == TagType::Mp4Ilst {
+            // Music.app classifies files by Apple-specific atoms that the
+            // generic ItemKeys don't cover: `stik` (media kind, 1 = music),
+            // `purd` (purchase/acquisition date) and the explicit `aART`
+            // album-artist atom.
+            let album_artist = tag.get_string(&ItemKey::AlbumArtist).map(str::to_string);
+            let release_date = tag.get_string(&ItemKey::ReleaseDate).map(str::to_string);
+
+            let generic = tagged_file
+                .remove(tag_type)
+                .ok_or_else(|| "Failed to get tag".to_string())?;
+            let mut ilst: Ilst = generic.into();
+
+            ilst.replace_atom(Atom::new(
+                AtomIdent::Fourcc(*b"stik"),
+                AtomData::UnsignedInteger(1),
+            ));
+            if let Some(date) = release_date {
+                ilst.replace_atom(Atom::new(AtomIdent::Fourcc(*b"purd"), AtomData::UTF8(date)));
+            }
+            if let Some(artist) = album_artist {
+                ilst.replace_atom(Atom::new(
+                    AtomIdent::Fourcc(*b"aART"),
+                    AtomData::UTF8(artist),
+                ));
+            }
+
+            ilst.save_to_path(path, WriteOptions::default())?;
+        } else {
+            tagged_file.save_to_path(path, WriteOptions::default())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credit_roles_classify_and_map_to_item_keys() {
+        assert_eq!(CreditRole::from_tidal_type("Producers"), CreditRole::Producer);
+        assert_eq!(
+            CreditRole::from_tidal_type("Mix Engineer"),
+            CreditRole::MixEngineer
+        );
+        assert_eq!(
+            CreditRole::from_tidal_type("RECORD LABEL"),
+            CreditRole::Label
+        );
+        assert_eq!(
+            CreditRole::from_tidal_type("A&R"),
+            CreditRole::Other("A&R".to_string())
+        );
+
+        assert_eq!(
+            CreditRole::Producer.to_item_key(),
+            Some(ItemKey::Producer)
+        );
+        assert_eq!(CreditRole::Performer.to_item_key(), None);
+        assert_eq!(
+            CreditRole::Other("A&R".to_string()).to_item_key(),
+            None
+        );
+    }
+
+    #[test]
+    fn append_comment_merges_with_separator() {
+        let mut tag = Tag::new(TagType::VorbisComments);
+        append_comment(&mut tag, "Performers: A");
+        append_comment(&mut tag, "A&R: B");
+        assert_eq!(
+            tag.get_string(&ItemKey::Comment),
+            Some("Performers: A | A&R: B")
+        );
+    }
+}
@@ -1,8 +1,12 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
 use super::client::TidalClient;
 use super::models::{
     Album,
+    BATCH_IDS_LIMIT,
+    BatchOutcome,
     AlbumItemsCreditsResponse,
     AlbumPage,
     AlbumReview,
@@ -20,22 +24,60 @@ impl TidalClient {
     }
 
     pub async fn get_albums(&mut self, album_ids: &[u64]) -> Result<Vec<Album>> {
-        if album_ids.is_empty() {
-            return Ok(vec![]);
-        }
-        let ids = album_ids
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<_>>()
-            .join(",");
-        let url = self.api_url("albums", &[("ids", &ids)]);
-
         #[derive(Deserialize)]
         struct AlbumsResponse {
             items: Vec<Album>,
         }
-        let resp: AlbumsResponse = self.get(&url).await?;
-        Ok(resp.items)
+
+        let mut albums = Vec::with_capacity(album_ids.len());
+        for chunk in album_ids.chunks(BATCH_IDS_LIMIT) {
+            let ids = chunk
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let url = self.api_url("albums", &[("ids", &ids)]);
+            let resp: AlbumsResponse = self.get(&url).await?;
+            albums.extend(resp.items);
+        }
+        Ok(albums)
+    }
+
+    /// Album counterpart of `get_tracks_partial`: fetch a large id set in
+    /// concurrent chunks, reporting unresolved ids in
+    /// [`BatchOutcome::failed`] instead of failing the whole call.
+    pub async fn get_albums_partial(&self, album_ids: &[u64]) -> BatchOutcome<Album> {
+        let fetches = album_ids
+            .chunks(BATCH_IDS_LIMIT)
+            .map(|chunk| {
+                let mut client = self.clone();
+                let chunk = chunk.to_vec();
+                async move {
+                    let result = client.get_albums(&chunk).await;
+                    (chunk, result)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut items = Vec::with_capacity(album_ids.len());
+        let mut failed = Vec::new();
+        for (chunk, result) in futures::future::join_all(fetches).await {
+            match result {
+                Ok(albums) => {
+                    let mut by_id: HashMap<u64, Album> =
+                        albums.into_iter().map(|a| (a.id, a)).collect();
+                    for id in chunk {
+                        match by_id.remove(&id) {
+                            Some(album) => items.push(album),
+                            None => failed.push((id, "not found".to_string())),
+                        }
+                    }
+                }
+                Err(e) => failed.extend(chunk.into_iter().map(|id| (id, e.to_string()))),
+            }
+        }
+
+        BatchOutcome { items, failed }
     }
 
     pub async fn get_album_tracks(
@@ -54,8 +96,38 @@ impl TidalClient {
         self.get(&url).await
     }
 
+    /// Every track of an album, paging past the 100-item cap so large
+    /// compilations come back complete.
+    pub async fn get_all_album_tracks(&mut self, album_id: u64) -> Result<Vec<Track>> {
+        let limit = 100u32;
+        let mut offset = 0u32;
+        let mut tracks = Vec::new();
+
+        loop {
+            let page = self.get_album_tracks(album_id, limit, offset).await?;
+            if page.items.is_empty() {
+                break;
+            }
+
+            tracks.extend(page.items);
+
+            if tracks.len() as u32 >= page.total {
+                break;
+            }
+            offset += limit;
+        }
+
+        Ok(tracks)
+    }
+
+    /// Album-level credits. Always passes `includeContributors=true` so
+    /// contributor objects carry their ids, matching what the items-credits
+    /// path returns.
     pub async fn get_album_credits(&mut self, album_id: u64) -> Result<Vec<Credit>> {
-        let url = self.api_url(&format!("albums/{}/credits", album_id), &[]);
+        let url = self.api_url(
+            &format!("albums/{}/credits", album_id),
+            &[("includeContributors", "true")],
+        );
         #[derive(Deserialize)]
         struct CreditsResponse {
             credits: Vec<Credit>,
@@ -105,6 +177,46 @@ impl TidalClient {
         Ok(all_credits)
     }
 
+    /// Fetch an album's tracks paired with each track's credits, matched by
+    /// track id. One aggregate for album detail views with expandable
+    /// credits; tracks the credits endpoint doesn't cover get an empty list.
+    pub async fn get_album_tracks_with_credits(
+        &mut self,
+        album_id: u64,
+    ) -> Result<Vec<(Track, Vec<Credit>)>> {
+        let tracks = self.get_all_album_tracks(album_id).await?;
+        let mut credits_by_track: std::collections::HashMap<u64, Vec<Credit>> = self
+            .get_all_album_track_credits(album_id)
+            .await?
+            .into_iter()
+            .map(|tc| (tc.item.id, tc.credits))
+            .collect();
+
+        Ok(tracks
+            .into_iter()
+            .map(|track| {
+                let credits = credits_by_track.remove(&track.id).unwrap_or_default();
+                (track, credits)
+            })
+            .collect())
+    }
+
+    /// Look up an album by UPC, the album-level counterpart of
+    /// [`get_track_by_isrc`](Self::get_track_by_isrc). Searches for the code
+    /// and returns the first album whose `upc` field matches; leading zeroes
+    /// are ignored on both sides, since catalogs disagree on UPC-A vs EAN-13
+    /// padding for the same barcode.
+    pub async fn get_album_by_upc(&mut self, upc: &str) -> Result<Option<Album>> {
+        let wanted = upc.trim_start_matches('0');
+        let page = self.search_albums(upc, 50, 0).await?;
+        Ok(page.items.into_iter().find(|album| {
+            album
+                .upc
+                .as_deref()
+                .is_some_and(|code| code.trim_start_matches('0') == wanted)
+        }))
+    }
+
     pub async fn get_album_review(&mut self, album_id: u64) -> Result<AlbumReview> {
         let url = self.api_url(&format!("albums/{}/review", album_id), &[]);
         self.get(&url).await
@@ -7,6 +7,7 @@ use super::models::{
     Artist,
     ArtistBio,
     ArtistLink,
+    ArtistPage,
     ItemsPage,
     Mix,
     Track,
@@ -76,6 +77,17 @@ impl TidalClient {
         self.get(&url).await
     }
 
+    /// Every album of an artist, draining the paged listing via
+    /// [`paginate_all`](super::client::paginate_all).
+    pub async fn artist_albums_all(&mut self, artist_id: u64) -> Result<Vec<Album>> {
+        let client = self.clone();
+        super::client::paginate_all(50, move |offset| {
+            let mut client = client.clone();
+            async move { client.get_artist_albums(artist_id, 50, offset).await }
+        })
+        .await
+    }
+
     pub async fn get_artist_top_tracks(
         &mut self,
         artist_id: u64,
@@ -108,6 +120,14 @@ impl TidalClient {
         self.get(&url).await
     }
 
+    /// Fetches the artist's full profile page (bio, top tracks, discography
+    /// highlights, related artists) as modules in a single request, mirroring
+    /// [`TidalClient::get_album_page`].
+    pub async fn get_artist_page(&mut self, artist_id: u64) -> Result<ArtistPage> {
+        let url = self.pages_url(&format!("artist?artistId={}", artist_id), &[]);
+        self.get(&url).await
+    }
+
     pub async fn get_similar_artists(
         &mut self,
         artist_id: u64,
@@ -1,11 +1,21 @@
 #![allow(dead_code)]
+use std::sync::{
+    Arc,
+    OnceLock,
+};
 use std::time::Duration;
 
 use serde::Deserialize;
+use tokio::sync::Semaphore;
 
+use super::models::{
+    ItemsPage,
+    SessionInfo,
+};
 use crate::core::auth::{
     AuthSession,
     CLIENT_TOKEN,
+    Credentials,
 };
 use crate::core::error::{
     Result,
@@ -13,7 +23,9 @@ use crate::core::error::{
 };
 
 pub(crate) const API_BASE: &str = "https://api.tidal.com/v1";
+pub(crate) const API_V2_BASE: &str = "https://api.tidal.com/v2";
 pub(crate) const LISTEN_API_BASE: &str = "https://listen.tidal.com/v1";
+pub(crate) const PAGES_BASE: &str = "https://tidal.com/v1/pages";
 pub(crate) const SUGGESTIONS_BASE: &str = "https://tidal.com/v2";
 
 #[derive(Debug, Clone)]
@@ -23,6 +35,23 @@ pub struct ClientConfig {
     pub retry_delay: Duration,
     pub user_agent: String,
     pub client_version: Option<String>,
+    /// Optional limiter shared by every clone of the client, capping the
+    /// total number of in-flight requests across the whole application.
+    pub shared_limiter: Option<Arc<Semaphore>>,
+    /// Base URLs for the API hosts. Override these to point the client at a
+    /// mock server, a proxy, or a regional mirror.
+    pub api_base: String,
+    pub api_v2_base: String,
+    pub listen_base: String,
+    pub pages_base: String,
+    pub suggestions_base: String,
+    /// Optional request-rate throttle shared by every clone of the client;
+    /// see [`with_rate_limit`](Self::with_rate_limit).
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+    /// Value of the `X-Tidal-Token` header; override together with matching
+    /// [`AuthSession::with_client`](crate::AuthSession::with_client)
+    /// credentials when using a custom client registration.
+    pub client_token: String,
 }
 
 impl Default for ClientConfig {
@@ -33,6 +62,14 @@ impl Default for ClientConfig {
             retry_delay: Duration::from_millis(500),
             user_agent: "TIDAL_ANDROID/1039 okhttp/3.14.9".to_string(),
             client_version: None,
+            shared_limiter: None,
+            api_base: API_BASE.to_string(),
+            api_v2_base: API_V2_BASE.to_string(),
+            listen_base: LISTEN_API_BASE.to_string(),
+            pages_base: PAGES_BASE.to_string(),
+            suggestions_base: SUGGESTIONS_BASE.to_string(),
+            rate_limiter: None,
+            client_token: CLIENT_TOKEN.to_string(),
         }
     }
 }
@@ -59,9 +96,111 @@ impl ClientConfig {
         self.client_version = Some(version.into());
         self
     }
+
+    /// Points every v1 API call at a different host, typically a mock server
+    /// in tests.
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into();
+        self
+    }
+
+    /// Sends a custom client token in the `X-Tidal-Token` header instead of
+    /// the built-in TV one.
+    pub fn with_client_token(mut self, client_token: impl Into<String>) -> Self {
+        self.client_token = client_token.into();
+        self
+    }
+
+    /// Caps the request rate at `requests_per_second` across every clone of
+    /// the client, with burst capacity equal to one second's allowance.
+    /// Every request method waits on the bucket before sending; left unset,
+    /// requests go out unthrottled as before. Complements
+    /// [`with_shared_limiter`](Self::with_shared_limiter), which caps
+    /// concurrency rather than rate.
+    pub fn with_rate_limit(mut self, requests_per_second: u32) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(requests_per_second)));
+        self
+    }
+
+    /// Caps concurrent requests across every client sharing this limiter.
+    /// Clones of a `TidalClient` keep the same `Arc`, so the cap applies
+    /// app-wide rather than per clone.
+    pub fn with_shared_limiter(mut self, limiter: Arc<Semaphore>) -> Self {
+        self.shared_limiter = Some(limiter);
+        self
+    }
 }
 
-#[derive(Debug, Clone)]
+/// Token-bucket request throttle. The bucket holds up to one second's worth
+/// of tokens, refilled continuously, so short bursts go straight through and
+/// sustained load settles at the configured rate.
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    state: tokio::sync::Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: std::time::Instant,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_second: u32) -> Self {
+        let rate = f64::from(requests_per_second.max(1));
+        Self {
+            requests_per_second: rate,
+            state: tokio::sync::Mutex::new(BucketState {
+                tokens: rate,
+                last_refill: std::time::Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a token is available, then consume it.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let now = std::time::Instant::now();
+                let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+                state.tokens = (state.tokens + elapsed * self.requests_per_second)
+                    .min(self.requests_per_second);
+                state.last_refill = now;
+                if state.tokens >= 1.0 {
+                    state.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - state.tokens) / self.requests_per_second)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Hook type for [`TidalClient::set_on_token_refresh`].
+pub type TokenRefreshCallback = Arc<dyn Fn(&Credentials) + Send + Sync>;
+
+/// Mutation verbs that share one retry loop; see
+/// [`TidalClient::send_with_retry`] for the policy.
+#[derive(Debug, Clone, Copy)]
+enum Verb {
+    Post,
+    Put,
+    Delete,
+}
+
+/// Request body for the mutation verbs, carrying its own content type. Most
+/// endpoints take JSON; the playlist metadata update wants its fields
+/// form-encoded.
+#[derive(Debug, Clone, Copy)]
+enum Payload<'a> {
+    Json(&'a str),
+    Form(&'a str),
+}
+
+#[derive(Clone)]
 pub struct TidalClient {
     pub(crate) client: reqwest::Client,
     pub access_token: String,
@@ -70,6 +209,86 @@ pub struct TidalClient {
     pub user_id: Option<u64>,
     pub(crate) config: ClientConfig,
     pub expires_at: u64,
+    /// Auth session reused for mid-flight token refreshes; set by
+    /// [`with_auto_refresh`](Self::with_auto_refresh). Without one, refreshes
+    /// fall back to a fresh session per attempt.
+    pub(crate) auth: Option<AuthSession>,
+    /// Fired after every successful token refresh so the application can
+    /// persist the new tokens; see
+    /// [`set_on_token_refresh`](Self::set_on_token_refresh).
+    pub(crate) on_token_refresh: Option<TokenRefreshCallback>,
+    pub(crate) session: OnceLock<SessionInfo>,
+}
+
+// Manual impl because the refresh callback is an unnameable closure; showing
+// whether one is registered is all a debug dump needs.
+impl std::fmt::Debug for TidalClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TidalClient")
+            .field("country_code", &self.country_code)
+            .field("user_id", &self.user_id)
+            .field("expires_at", &self.expires_at)
+            .field("config", &self.config)
+            .field("auth", &self.auth)
+            .field("on_token_refresh", &self.on_token_refresh.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Detect a body that's still gzip-compressed. reqwest decompresses
+/// transparently when its `gzip` feature is enabled; if that ever regresses
+/// (feature dropped, unified-body proxy, etc.) the JSON parse would fail with
+/// a cryptic "expected value" error, so check for the gzip magic up front.
+/// `text()` lossily replaces the 0x8b magic byte with U+FFFD, hence the
+/// two-part check.
+fn check_not_gzip(text: &str) -> Result<()> {
+    if text.as_bytes().first() == Some(&0x1f) && text[1..].starts_with('\u{FFFD}') {
+        return Err(TidalError::Decode(
+            "Response body is still gzip-compressed; reqwest's `gzip` feature \
+             appears to be disabled"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Parse a `Retry-After` header into a wait. Only the delta-seconds form is
+/// handled — Tidal sends seconds, never an HTTP-date.
+fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Map a non-success response to the right error variant. 412 gets its own
+/// [`TidalError::Precondition`] so ETag-guarded mutation paths can catch a
+/// stale precondition precisely and refresh-and-retry; 429 and 503 become
+/// [`TidalError::RateLimited`] carrying the server's `Retry-After`, which
+/// the retry loop honors; everything else stays a generic
+/// [`TidalError::Api`].
+fn api_error(
+    status: reqwest::StatusCode,
+    retry_after: Option<Duration>,
+    text: &str,
+) -> TidalError {
+    let message = text[..text.len().min(200)].to_string();
+    if status == reqwest::StatusCode::PRECONDITION_FAILED {
+        TidalError::Precondition(message)
+    } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::SERVICE_UNAVAILABLE
+    {
+        TidalError::RateLimited { retry_after }
+    } else {
+        TidalError::Api {
+            status: status.as_u16(),
+            message,
+        }
+    }
 }
 
 impl TidalClient {
@@ -101,9 +320,58 @@ impl TidalClient {
             user_id: None,
             config,
             expires_at: 0,
+            auth: None,
+            on_token_refresh: None,
+            session: OnceLock::new(),
         }
     }
 
+    /// Build a client that refreshes its own tokens: proactively when
+    /// `expires_at` passes, and reactively when a request comes back 401
+    /// (the request is retried once with the new token). `session` keeps its
+    /// `client_unique_key` stable across refreshes instead of minting a new
+    /// one per attempt.
+    pub fn with_auto_refresh(credentials: Credentials, session: AuthSession) -> Self {
+        let mut client = Self::from_credentials(credentials);
+        client.auth = Some(session);
+        client
+    }
+
+    /// Build a client from persisted [`Credentials`], restoring the token
+    /// expiry and user id a plain [`new`](Self::new) doesn't carry.
+    pub fn from_credentials(credentials: Credentials) -> Self {
+        let mut client = Self::new(
+            credentials.access_token,
+            credentials.refresh_token,
+            credentials.country_code,
+        );
+        client.expires_at = credentials.expires_at;
+        client.user_id = credentials.user_id;
+        client
+    }
+
+    /// Snapshot the session state as [`Credentials`] for persisting, the
+    /// inverse of [`from_credentials`](Self::from_credentials).
+    pub fn to_credentials(&self) -> Credentials {
+        Credentials {
+            access_token: self.access_token.clone(),
+            refresh_token: self.refresh_token.clone(),
+            expires_at: self.expires_at,
+            user_id: self.user_id,
+            country_code: self.country_code.clone(),
+        }
+    }
+
+    /// Register a hook that fires with the new [`Credentials`] after every
+    /// successful token refresh (both the proactive expiry check and the
+    /// 401 retry path), so updated tokens can be persisted before the old
+    /// ones stop working. The callback runs inline on the requesting task:
+    /// keep it cheap and non-blocking, and hand anything slow to a channel
+    /// or spawned task.
+    pub fn set_on_token_refresh(&mut self, callback: TokenRefreshCallback) {
+        self.on_token_refresh = Some(callback);
+    }
+
     pub fn with_expiry(mut self, expires_at: u64) -> Self {
         self.expires_at = expires_at;
         self
@@ -113,6 +381,21 @@ impl TidalClient {
         &self.config
     }
 
+    /// Returns the cached session from a previous `get_session` call, if any,
+    /// without making a network request.
+    pub fn session(&self) -> Option<&SessionInfo> {
+        self.session.get()
+    }
+
+    /// The country code to use for requests: the session's if one has been
+    /// fetched, otherwise whatever the client was constructed with.
+    pub(crate) fn effective_country(&self) -> &str {
+        self.session
+            .get()
+            .map(|s| s.country_code.as_str())
+            .unwrap_or(self.country_code.as_str())
+    }
+
     pub fn update_tokens(
         &mut self,
         access_token: String,
@@ -129,7 +412,7 @@ impl TidalClient {
     }
 
     pub async fn refresh_tokens(&mut self) -> Result<()> {
-        let auth = AuthSession::new();
+        let auth = self.auth.clone().unwrap_or_default();
         let response = auth.refresh_token(&self.refresh_token).await?;
 
         self.access_token = response.access_token;
@@ -142,6 +425,10 @@ impl TidalClient {
             .as_secs()
             + response.expires_in;
 
+        if let Some(callback) = &self.on_token_refresh {
+            callback(&self.to_credentials());
+        }
+
         Ok(())
     }
 
@@ -168,7 +455,8 @@ impl TidalClient {
 
         headers.insert(
             "X-Tidal-Token",
-            CLIENT_TOKEN
+            self.config
+                .client_token
                 .parse()
                 .map_err(|_| TidalError::Auth("Invalid client token".into()))?,
         );
@@ -203,17 +491,21 @@ impl TidalClient {
         self.ensure_valid_token().await?;
 
         let mut last_error = None;
+        let mut next_delay: Option<Duration> = None;
 
         for attempt in 0..=self.config.max_retries {
             if attempt > 0 {
-                tokio::time::sleep(self.config.retry_delay * attempt).await;
+                let delay = next_delay
+                    .take()
+                    .unwrap_or(self.config.retry_delay * attempt);
+                tokio::time::sleep(delay).await;
             }
 
             match self.get_once::<T>(url).await {
                 Ok(result) => return Ok(result),
                 Err(e) => {
-                    if let TidalError::Api { status: 401, .. } = &e {
-                        if let Ok(()) = self.refresh_tokens().await {
+                    if let TidalError::Api { status: 401, .. } = &e
+                        && let Ok(()) = self.refresh_tokens().await {
                             match self.get_once::<T>(url).await {
                                 Ok(result) => return Ok(result),
                                 Err(retry_err) => {
@@ -222,9 +514,26 @@ impl TidalClient {
                                 }
                             }
                         }
+
+                    // Throttled: wait out the server's Retry-After when it
+                    // sent one, otherwise back off exponentially from the
+                    // configured delay.
+                    if let TidalError::RateLimited { retry_after } = &e
+                        && attempt < self.config.max_retries
+                    {
+                        next_delay = Some(retry_after.unwrap_or_else(|| {
+                            self.config.retry_delay * 2u32.saturating_pow(attempt + 1)
+                        }));
+                        last_error = Some(e);
+                        continue;
                     }
 
-                    if matches!(e, TidalError::Network(_)) && attempt < self.config.max_retries {
+                    // Transient gateway failures are worth retrying; 4xx
+                    // responses (bad request, not found, forbidden) never
+                    // are.
+                    let transient = matches!(e, TidalError::Network(_))
+                        || matches!(e, TidalError::Api { status: 500 | 502 | 504, .. });
+                    if transient && attempt < self.config.max_retries {
                         last_error = Some(e);
                         continue;
                     }
@@ -239,18 +548,57 @@ impl TidalClient {
         }))
     }
 
-    async fn get_once<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
+    pub(crate) async fn throttle(&self) -> Option<tokio::sync::OwnedSemaphorePermit> {
+        if let Some(limiter) = &self.config.rate_limiter {
+            limiter.acquire().await;
+        }
+        match &self.config.shared_limiter {
+            Some(limiter) => limiter.clone().acquire_owned().await.ok(),
+            None => None,
+        }
+    }
+
+    pub(crate) async fn get_once<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
+        let _permit = self.throttle().await;
         let resp = self.client.get(url).headers(self.headers()?).send().await?;
         let status = resp.status();
+        let retry_after = parse_retry_after(resp.headers());
+        let text = resp.text().await?;
+
+        if !status.is_success() {
+            return Err(api_error(status, retry_after, &text));
+        }
+
+        check_not_gzip(&text)?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// Like [`get_once`](Self::get_once), but authorized with `access_token`
+    /// instead of the client's own. Lets `&self` callers retry with a freshly
+    /// minted token they can't store back on the client.
+    pub(crate) async fn get_once_with_token<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        access_token: &str,
+    ) -> Result<T> {
+        let _permit = self.throttle().await;
+        let mut headers = self.headers()?;
+        headers.insert(
+            reqwest::header::AUTHORIZATION,
+            format!("Bearer {}", access_token)
+                .parse()
+                .map_err(|_| TidalError::Auth("Invalid access token".into()))?,
+        );
+        let resp = self.client.get(url).headers(headers).send().await?;
+        let status = resp.status();
+        let retry_after = parse_retry_after(resp.headers());
         let text = resp.text().await?;
 
         if !status.is_success() {
-            return Err(TidalError::Api {
-                status: status.as_u16(),
-                message: text[..text.len().min(200)].to_string(),
-            });
+            return Err(api_error(status, retry_after, &text));
         }
 
+        check_not_gzip(&text)?;
         Ok(serde_json::from_str(&text)?)
     }
 
@@ -258,191 +606,251 @@ impl TidalClient {
         self.get_with_retry(url).await
     }
 
-    pub(crate) async fn post<T: for<'de> Deserialize<'de>>(
+    /// Like `get`, but also returns the response's `ETag` header, which the
+    /// playlist endpoints use for change detection and conditional updates.
+    pub(crate) async fn get_with_etag<T: for<'de> Deserialize<'de>>(
         &mut self,
         url: &str,
-        body: Option<&str>,
-    ) -> Result<T> {
+    ) -> Result<(T, Option<String>)> {
         self.ensure_valid_token().await?;
+        let _permit = self.throttle().await;
 
-        let mut req = self.client.post(url).headers(self.headers()?);
-        if let Some(b) = body {
-            req = req
-                .header(reqwest::header::CONTENT_TYPE, "application/json")
-                .body(b.to_string());
-        }
-        let resp = req.send().await?;
+        let resp = self.client.get(url).headers(self.headers()?).send().await?;
         let status = resp.status();
+        let retry_after = parse_retry_after(resp.headers());
+        let etag = resp
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
         let text = resp.text().await?;
 
-        if status == reqwest::StatusCode::UNAUTHORIZED {
-            self.refresh_tokens().await?;
-            let mut req = self.client.post(url).headers(self.headers()?);
-            if let Some(b) = body {
-                req = req
-                    .header(reqwest::header::CONTENT_TYPE, "application/json")
-                    .body(b.to_string());
-            }
-            let resp = req.send().await?;
-            let status = resp.status();
-            let text = resp.text().await?;
-
-            if !status.is_success() {
-                return Err(TidalError::Api {
-                    status: status.as_u16(),
-                    message: text[..text.len().min(200)].to_string(),
-                });
-            }
-
-            return Ok(serde_json::from_str(&text)?);
-        }
-
         if !status.is_success() {
-            return Err(TidalError::Api {
-                status: status.as_u16(),
-                message: text[..text.len().min(200)].to_string(),
-            });
+            return Err(api_error(status, retry_after, &text));
         }
 
-        Ok(serde_json::from_str(&text)?)
+        check_not_gzip(&text)?;
+        Ok((serde_json::from_str(&text)?, etag))
     }
 
-    pub(crate) async fn post_empty(&mut self, url: &str, body: Option<&str>) -> Result<()> {
-        self.ensure_valid_token().await?;
-
-        let mut req = self.client.post(url).headers(self.headers()?);
-        if let Some(b) = body {
-            req = req
-                .header(reqwest::header::CONTENT_TYPE, "application/json")
-                .body(b.to_string());
+    /// HTTP verbs routed through [`send_with_retry`](Self::send_with_retry).
+    async fn send_once(
+        &self,
+        verb: Verb,
+        url: &str,
+        body: Option<Payload<'_>>,
+        etag: Option<&str>,
+    ) -> std::result::Result<reqwest::Response, reqwest::Error> {
+        let mut req = match verb {
+            Verb::Post => self.client.post(url),
+            Verb::Put => self.client.put(url),
+            Verb::Delete => self.client.delete(url),
+        };
+        req = req.headers(self.headers().expect("headers validated by caller"));
+        if let Some(etag) = etag {
+            req = req.header(reqwest::header::IF_NONE_MATCH, etag);
         }
-        let resp = req.send().await?;
-        let status = resp.status();
-
-        if status == reqwest::StatusCode::UNAUTHORIZED {
-            self.refresh_tokens().await?;
-            let mut req = self.client.post(url).headers(self.headers()?);
-            if let Some(b) = body {
+        match body {
+            Some(Payload::Json(b)) => {
                 req = req
                     .header(reqwest::header::CONTENT_TYPE, "application/json")
                     .body(b.to_string());
             }
-            let resp = req.send().await?;
-            let status = resp.status();
-
-            if !status.is_success() {
-                let text = resp.text().await?;
-                return Err(TidalError::Api {
-                    status: status.as_u16(),
-                    message: text[..text.len().min(200)].to_string(),
-                });
+            Some(Payload::Form(b)) => {
+                req = req
+                    .header(
+                        reqwest::header::CONTENT_TYPE,
+                        "application/x-www-form-urlencoded",
+                    )
+                    .body(b.to_string());
             }
-
-            return Ok(());
-        }
-
-        if !status.is_success() {
-            let text = resp.text().await?;
-            return Err(TidalError::Api {
-                status: status.as_u16(),
-                message: text[..text.len().min(200)].to_string(),
-            });
+            None => {}
         }
-
-        Ok(())
+        req.send().await
     }
 
-    pub(crate) async fn put_empty(&mut self, url: &str, body: Option<&str>) -> Result<()> {
+    /// Shared retry loop for the mutation verbs.
+    ///
+    /// Retry policy, per failure class:
+    /// - 401: refresh tokens and retry once, same as the GET path.
+    /// - 429/503: always retried (honoring `Retry-After`) — a throttled
+    ///   request was rejected before being processed, so this is safe even
+    ///   for non-idempotent POSTs.
+    /// - 500/502/504 and post-send network errors: retried only when
+    ///   `idempotent` — once a POST has gone out, a gateway error is
+    ///   ambiguous about whether the mutation happened, and retrying could
+    ///   apply it twice (e.g. duplicating an add-to-playlist).
+    /// - Connection-level errors (the request never reached the server):
+    ///   always retried.
+    async fn send_with_retry(
+        &mut self,
+        verb: Verb,
+        url: &str,
+        body: Option<Payload<'_>>,
+        etag: Option<&str>,
+        idempotent: bool,
+    ) -> Result<String> {
         self.ensure_valid_token().await?;
+        // Validate headers up front so send_once can't panic.
+        self.headers()?;
 
-        let mut req = self.client.put(url).headers(self.headers()?);
-        if let Some(b) = body {
-            req = req
-                .header(reqwest::header::CONTENT_TYPE, "application/json")
-                .body(b.to_string());
-        }
-        let resp = req.send().await?;
-        let status = resp.status();
+        let mut last_error = None;
+        let mut next_delay: Option<Duration> = None;
+        let mut refreshed = false;
 
-        if status == reqwest::StatusCode::UNAUTHORIZED {
-            self.refresh_tokens().await?;
-            let mut req = self.client.put(url).headers(self.headers()?);
-            if let Some(b) = body {
-                req = req
-                    .header(reqwest::header::CONTENT_TYPE, "application/json")
-                    .body(b.to_string());
+        for attempt in 0..=self.config.max_retries {
+            if attempt > 0 {
+                let delay = next_delay
+                    .take()
+                    .unwrap_or(self.config.retry_delay * attempt);
+                tokio::time::sleep(delay).await;
             }
-            let resp = req.send().await?;
-            let status = resp.status();
 
-            if !status.is_success() {
-                let text = resp.text().await?;
-                return Err(TidalError::Api {
-                    status: status.as_u16(),
-                    message: text[..text.len().min(200)].to_string(),
+            let _permit = self.throttle().await;
+            let resp = match self.send_once(verb, url, body, etag).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    let retryable = idempotent || e.is_connect();
+                    if retryable && attempt < self.config.max_retries {
+                        last_error = Some(TidalError::Network(e));
+                        continue;
+                    }
+                    return Err(e.into());
+                }
+            };
+
+            let status = resp.status();
+            let retry_after = parse_retry_after(resp.headers());
+
+            if status == reqwest::StatusCode::UNAUTHORIZED && !refreshed {
+                self.refresh_tokens().await?;
+                self.headers()?;
+                refreshed = true;
+                next_delay = Some(Duration::ZERO);
+                last_error = Some(TidalError::Api {
+                    status: 401,
+                    message: "Unauthorized".into(),
                 });
+                continue;
             }
 
-            return Ok(());
-        }
+            if status.is_success() {
+                let text = resp.text().await?;
+                check_not_gzip(&text)?;
+                return Ok(text);
+            }
 
-        if !status.is_success() {
             let text = resp.text().await?;
-            return Err(TidalError::Api {
-                status: status.as_u16(),
-                message: text[..text.len().min(200)].to_string(),
-            });
+            let error = api_error(status, retry_after, &text);
+
+            let transient = match &error {
+                TidalError::RateLimited { .. } => true,
+                TidalError::Api {
+                    status: 500 | 502 | 504,
+                    ..
+                } => idempotent,
+                _ => false,
+            };
+            if transient && attempt < self.config.max_retries {
+                if let TidalError::RateLimited { retry_after } = &error {
+                    next_delay = retry_after.or(next_delay);
+                }
+                last_error = Some(error);
+                continue;
+            }
+            return Err(error);
         }
 
-        Ok(())
+        Err(last_error.unwrap_or_else(|| TidalError::Api {
+            status: 0,
+            message: "Max retries exceeded".into(),
+        }))
     }
 
-    pub(crate) async fn delete_empty(&mut self, url: &str) -> Result<()> {
-        self.ensure_valid_token().await?;
+    /// POSTs are not idempotent: only connection-level and
+    /// rejected-before-processing failures are retried (see
+    /// [`send_with_retry`](Self::send_with_retry)).
+    pub(crate) async fn post<T: for<'de> Deserialize<'de>>(
+        &mut self,
+        url: &str,
+        body: Option<&str>,
+    ) -> Result<T> {
+        let text = self
+            .send_with_retry(Verb::Post, url, body.map(Payload::Json), None, false)
+            .await?;
+        Ok(serde_json::from_str(&text)?)
+    }
 
-        let resp = self
-            .client
-            .delete(url)
-            .headers(self.headers()?)
-            .send()
+    /// See [`post`](Self::post) for the (limited) retry semantics.
+    pub(crate) async fn post_empty(&mut self, url: &str, body: Option<&str>) -> Result<()> {
+        self.send_with_retry(Verb::Post, url, body.map(Payload::Json), None, false)
             .await?;
-        let status = resp.status();
+        Ok(())
+    }
 
-        if status == reqwest::StatusCode::UNAUTHORIZED {
-            self.refresh_tokens().await?;
-            let resp = self
-                .client
-                .delete(url)
-                .headers(self.headers()?)
-                .send()
-                .await?;
-            let status = resp.status();
+    /// PUTs here are idempotent (favoriting twice is a no-op), so transient
+    /// 5xx and network failures are retried in full.
+    pub(crate) async fn put_empty(&mut self, url: &str, body: Option<&str>) -> Result<()> {
+        self.send_with_retry(Verb::Put, url, body.map(Payload::Json), None, true)
+            .await?;
+        Ok(())
+    }
 
-            if !status.is_success() {
-                let text = resp.text().await?;
-                return Err(TidalError::Api {
-                    status: status.as_u16(),
-                    message: text[..text.len().min(200)].to_string(),
-                });
-            }
+    /// DELETEs are idempotent, so transient 5xx and network failures are
+    /// retried in full.
+    pub(crate) async fn delete_empty(&mut self, url: &str) -> Result<()> {
+        self.send_with_retry(Verb::Delete, url, None, None, true).await?;
+        Ok(())
+    }
 
-            return Ok(());
-        }
+    /// POST guarded by an `If-None-Match` precondition, as the playlist
+    /// mutation endpoints require. A stale ETag surfaces as
+    /// [`TidalError::Precondition`] for the caller to refresh and retry.
+    pub(crate) async fn post_empty_with_etag(
+        &mut self,
+        url: &str,
+        body: Option<&str>,
+        etag: Option<&str>,
+    ) -> Result<()> {
+        self.send_with_retry(Verb::Post, url, body.map(Payload::Json), etag, false)
+            .await?;
+        Ok(())
+    }
 
-        if !status.is_success() {
-            let text = resp.text().await?;
-            return Err(TidalError::Api {
-                status: status.as_u16(),
-                message: text[..text.len().min(200)].to_string(),
-            });
-        }
+    /// POST a form-encoded body guarded by an `If-None-Match` precondition.
+    /// The playlist metadata update endpoint takes its fields as
+    /// `application/x-www-form-urlencoded` body parameters, not JSON and not
+    /// query parameters.
+    pub(crate) async fn post_form_with_etag(
+        &mut self,
+        url: &str,
+        form: &[(&str, &str)],
+        etag: Option<&str>,
+    ) -> Result<()> {
+        let body = form
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+        self.send_with_retry(Verb::Post, url, Some(Payload::Form(&body)), etag, false)
+            .await?;
+        Ok(())
+    }
 
+    /// DELETE guarded by an `If-None-Match` precondition; see
+    /// [`post_empty_with_etag`](Self::post_empty_with_etag).
+    pub(crate) async fn delete_empty_with_etag(
+        &mut self,
+        url: &str,
+        etag: Option<&str>,
+    ) -> Result<()> {
+        self.send_with_retry(Verb::Delete, url, None, etag, true).await?;
         Ok(())
     }
 
     pub(crate) fn api_url(&self, path: &str, extra_params: &[(&str, &str)]) -> String {
         let mut params = vec![
-            ("countryCode", self.country_code.as_str()),
+            ("countryCode", self.effective_country()),
             ("locale", "en_US"),
             ("deviceType", "TV"),
         ];
@@ -454,12 +862,25 @@ impl TidalClient {
             .collect::<Vec<_>>()
             .join("&");
 
-        format!("{}/{}?{}", API_BASE, path, query)
+        format!("{}/{}?{}", self.config.api_base, path, query)
+    }
+
+    pub(crate) fn api_v2_url(&self, path: &str, extra_params: &[(&str, &str)]) -> String {
+        let mut params = vec![("countryCode", self.effective_country()), ("locale", "en_US")];
+        params.extend_from_slice(extra_params);
+
+        let query = params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        format!("{}/{}?{}", self.config.api_v2_base, path, query)
     }
 
     pub(crate) fn listen_url(&self, path: &str, extra_params: &[(&str, &str)]) -> String {
         let mut params = vec![
-            ("countryCode", self.country_code.as_str()),
+            ("countryCode", self.effective_country()),
             ("locale", "en_US"),
             ("deviceType", "TV"),
         ];
@@ -471,12 +892,12 @@ impl TidalClient {
             .collect::<Vec<_>>()
             .join("&");
 
-        format!("{}/{}?{}", LISTEN_API_BASE, path, query)
+        format!("{}/{}?{}", self.config.listen_base, path, query)
     }
 
     pub(crate) fn pages_url(&self, path: &str, extra_params: &[(&str, &str)]) -> String {
         let mut params = vec![
-            ("countryCode", self.country_code.as_str()),
+            ("countryCode", self.effective_country()),
             ("locale", "en_US"),
             ("deviceType", "BROWSER"),
         ];
@@ -489,17 +910,191 @@ impl TidalClient {
             .join("&");
 
         let separator = if path.contains('?') { "&" } else { "?" };
-        format!("https://tidal.com/v1/pages/{}{}{}", path, separator, query)
+        format!("{}/{}{}{}", self.config.pages_base, path, separator, query)
     }
 
     pub(crate) fn suggestions_url(&self, query: &str, explicit: bool, hybrid: bool) -> String {
         format!(
             "{}/suggestions/?countryCode={}&explicit={}&hybrid={}&query={}",
-            SUGGESTIONS_BASE,
-            self.country_code,
+            self.config.suggestions_base,
+            self.effective_country(),
             explicit,
             hybrid,
             urlencoding::encode(query)
         )
     }
 }
+
+/// Drain any offset/limit-paged endpoint that returns [`ItemsPage`].
+///
+/// `fetch` is called with the next offset until a page comes back shorter
+/// than `limit` or the accumulated count reaches the page's `total`. API
+/// methods take `&mut self`, so capture a clone of the client in the
+/// closure:
+///
+/// ```ignore
+/// let client = my_client.clone();
+/// let tracks = paginate_all(50, move |offset| {
+///     let mut client = client.clone();
+///     async move { client.get_album_tracks(album_id, 50, offset).await }
+/// })
+/// .await?;
+/// ```
+///
+/// Ready-made wrappers exist for the common listings, e.g.
+/// [`TidalClient::favorite_tracks_all`] and
+/// [`TidalClient::artist_albums_all`].
+pub async fn paginate_all<T, F, Fut>(limit: u32, mut fetch: F) -> Result<Vec<T>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<ItemsPage<T>>>,
+{
+    let mut items = Vec::new();
+    let mut offset = 0u32;
+
+    loop {
+        let page = fetch(offset).await?;
+        let fetched = page.items.len();
+        items.extend(page.items);
+
+        if fetched < limit as usize || items.len() as u32 >= page.total {
+            break;
+        }
+        offset += limit;
+    }
+
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve each canned `(status_line, body)` response to one connection in
+    /// order, then stop. Returns the bound address.
+    fn mock_server(responses: Vec<(&'static str, &'static str)>) -> std::net::SocketAddr {
+        use std::io::{
+            Read,
+            Write,
+        };
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || {
+            for (status_line, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status_line,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        addr
+    }
+
+    #[tokio::test]
+    async fn get_retries_through_transient_5xx() {
+        let addr = mock_server(vec![
+            ("502 Bad Gateway", "upstream hiccup"),
+            ("502 Bad Gateway", "upstream hiccup"),
+            ("200 OK", r#"{"value":42}"#),
+        ]);
+
+        let config = ClientConfig::default()
+            .with_retries(3, Duration::from_millis(1))
+            .with_api_base(format!("http://{}", addr));
+        let mut client =
+            TidalClient::with_config("token".into(), "refresh".into(), "US".into(), config);
+
+        let url = client.api_url("ping", &[]);
+        let value: serde_json::Value = client.get(&url).await.unwrap();
+        assert_eq!(value["value"], 42);
+    }
+
+    #[tokio::test]
+    async fn get_does_not_retry_client_errors() {
+        let addr = mock_server(vec![("404 Not Found", "no such thing")]);
+
+        let config = ClientConfig::default()
+            .with_retries(3, Duration::from_millis(1))
+            .with_api_base(format!("http://{}", addr));
+        let mut client =
+            TidalClient::with_config("token".into(), "refresh".into(), "US".into(), config);
+
+        let url = client.api_url("missing", &[]);
+        let err = client.get::<serde_json::Value>(&url).await.unwrap_err();
+        assert!(matches!(err, TidalError::Api { status: 404, .. }), "{err}");
+    }
+
+    #[tokio::test]
+    async fn idempotent_mutations_retry_but_posts_do_not() {
+        let addr = mock_server(vec![
+            ("502 Bad Gateway", "upstream hiccup"),
+            ("200 OK", ""),
+        ]);
+        let config = ClientConfig::default()
+            .with_retries(3, Duration::from_millis(1))
+            .with_api_base(format!("http://{}", addr));
+        let mut client =
+            TidalClient::with_config("token".into(), "refresh".into(), "US".into(), config);
+        let url = client.api_url("favorites/tracks/1", &[]);
+        client.put_empty(&url, None).await.unwrap();
+
+        // The same 502 aborts a POST: the mutation may already have applied.
+        let addr = mock_server(vec![("502 Bad Gateway", "upstream hiccup")]);
+        let config = ClientConfig::default()
+            .with_retries(3, Duration::from_millis(1))
+            .with_api_base(format!("http://{}", addr));
+        let mut client =
+            TidalClient::with_config("token".into(), "refresh".into(), "US".into(), config);
+        let url = client.api_url("playlists/x/items", &[]);
+        let err = client.post_empty(&url, None).await.unwrap_err();
+        assert!(matches!(err, TidalError::Api { status: 502, .. }), "{err}");
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_spaces_out_requests_past_the_burst() {
+        let limiter = RateLimiter::new(2);
+        let start = std::time::Instant::now();
+        // The bucket starts full (one second's allowance = 2 tokens), so the
+        // first two acquires are immediate; the third has to wait ~500ms.
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(200));
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn paginate_all_stops_on_short_page_and_total() {
+        let pages = vec![
+            ItemsPage { items: vec![1, 2, 3], total: 7, limit: Some(3), offset: Some(0) },
+            ItemsPage { items: vec![4, 5, 6], total: 7, limit: Some(3), offset: Some(3) },
+            ItemsPage { items: vec![7], total: 7, limit: Some(3), offset: Some(6) },
+        ];
+        let pages = std::sync::Arc::new(std::sync::Mutex::new(pages.into_iter()));
+        let offsets = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let all = {
+            let pages = pages.clone();
+            let offsets = offsets.clone();
+            paginate_all(3, move |offset| {
+                offsets.lock().unwrap().push(offset);
+                let page = pages.lock().unwrap().next().unwrap();
+                async move { Ok(page) }
+            })
+            .await
+            .unwrap()
+        };
+
+        assert_eq!(all, vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(*offsets.lock().unwrap(), vec![0, 3, 6]);
+    }
+}
@@ -4,12 +4,17 @@ use super::client::TidalClient;
 use super::models::{
     Genre,
     ItemsPage,
+    Lyrics,
     Mood,
+    Page,
     Playlist,
     Track,
     Video,
 };
-use crate::core::error::Result;
+use crate::core::error::{
+    Result,
+    TidalError,
+};
 
 impl TidalClient {
     pub async fn get_genres(&mut self) -> Result<Vec<Genre>> {
@@ -64,8 +69,38 @@ impl TidalClient {
         self.get(&url).await
     }
 
+    /// Fetch any dynamic page by its path (`home`, `explore`, `genre_rap`,
+    /// ...) as a module tree; see [`Page`] for how to consume it.
+    pub async fn get_page(&mut self, path: &str) -> Result<Page> {
+        let url = self.pages_url(path, &[]);
+        self.get(&url).await
+    }
+
+    /// The personalized home page: featured playlists, suggested new
+    /// releases, mixes and editorial rows.
+    pub async fn get_home_page(&mut self) -> Result<Page> {
+        self.get_page("home").await
+    }
+
+    /// The (non-personalized) explore page.
+    pub async fn get_explore_page(&mut self) -> Result<Page> {
+        self.get_page("explore").await
+    }
+
     pub async fn get_video(&mut self, video_id: u64) -> Result<Video> {
         let url = self.api_url(&format!("videos/{}", video_id), &[]);
         self.get(&url).await
     }
+
+    /// Fetches timed lyrics for a video, if Tidal has them. Most videos carry
+    /// no lyrics at all, which the API reports as a 404; that case is mapped
+    /// to `Ok(None)` so callers can distinguish "no lyrics" from real errors.
+    pub async fn get_video_lyrics(&mut self, video_id: u64) -> Result<Option<Lyrics>> {
+        let url = self.api_url(&format!("videos/{}/lyrics", video_id), &[]);
+        match self.get(&url).await {
+            Ok(lyrics) => Ok(Some(lyrics)),
+            Err(TidalError::Api { status: 404, .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
 }
@@ -1,15 +1,34 @@
+use std::collections::HashSet;
+
 use super::client::TidalClient;
 use super::models::{
     Album,
     Artist,
+    FavoriteDiff,
     FavoriteIds,
     FavoriteItem,
+    FavoriteOrder,
     ItemsPage,
+    Mix,
+    OrderDirection,
     Playlist,
     Track,
     Video,
 };
-use crate::core::error::Result;
+use crate::core::error::{
+    Result,
+    TidalError,
+};
+
+/// Adding something that's already a favorite can come back as a 409. For
+/// sync tools that re-run, "already present" is success, so the add methods
+/// swallow the conflict instead of surfacing (or retrying) it.
+fn ignore_conflict(result: Result<()>) -> Result<()> {
+    match result {
+        Err(TidalError::Api { status: 409, .. }) => Ok(()),
+        other => other,
+    }
+}
 
 impl TidalClient {
     pub async fn get_favorite_tracks(
@@ -17,14 +36,16 @@ impl TidalClient {
         user_id: u64,
         limit: u32,
         offset: u32,
+        order: FavoriteOrder,
+        direction: OrderDirection,
     ) -> Result<ItemsPage<FavoriteItem<Track>>> {
         let url = self.api_url(
             &format!("users/{}/favorites/tracks", user_id),
             &[
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
-                ("order", "DATE"),
-                ("orderDirection", "DESC"),
+                ("order", order.as_str()),
+                ("orderDirection", direction.as_str()),
             ],
         );
         self.get(&url).await
@@ -35,14 +56,16 @@ impl TidalClient {
         user_id: u64,
         limit: u32,
         offset: u32,
+        order: FavoriteOrder,
+        direction: OrderDirection,
     ) -> Result<ItemsPage<FavoriteItem<Album>>> {
         let url = self.api_url(
             &format!("users/{}/favorites/albums", user_id),
             &[
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
-                ("order", "DATE"),
-                ("orderDirection", "DESC"),
+                ("order", order.as_str()),
+                ("orderDirection", direction.as_str()),
             ],
         );
         self.get(&url).await
@@ -53,14 +76,16 @@ impl TidalClient {
         user_id: u64,
         limit: u32,
         offset: u32,
+        order: FavoriteOrder,
+        direction: OrderDirection,
     ) -> Result<ItemsPage<FavoriteItem<Artist>>> {
         let url = self.api_url(
             &format!("users/{}/favorites/artists", user_id),
             &[
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
-                ("order", "DATE"),
-                ("orderDirection", "DESC"),
+                ("order", order.as_str()),
+                ("orderDirection", direction.as_str()),
             ],
         );
         self.get(&url).await
@@ -71,14 +96,16 @@ impl TidalClient {
         user_id: u64,
         limit: u32,
         offset: u32,
+        order: FavoriteOrder,
+        direction: OrderDirection,
     ) -> Result<ItemsPage<FavoriteItem<Playlist>>> {
         let url = self.api_url(
             &format!("users/{}/favorites/playlists", user_id),
             &[
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
-                ("order", "DATE"),
-                ("orderDirection", "DESC"),
+                ("order", order.as_str()),
+                ("orderDirection", direction.as_str()),
             ],
         );
         self.get(&url).await
@@ -89,30 +116,103 @@ impl TidalClient {
         user_id: u64,
         limit: u32,
         offset: u32,
+        order: FavoriteOrder,
+        direction: OrderDirection,
     ) -> Result<ItemsPage<FavoriteItem<Video>>> {
         let url = self.api_url(
             &format!("users/{}/favorites/videos", user_id),
             &[
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
-                ("order", "DATE"),
-                ("orderDirection", "DESC"),
+                ("order", order.as_str()),
+                ("orderDirection", direction.as_str()),
+            ],
+        );
+        self.get(&url).await
+    }
+
+    pub async fn get_favorite_mixes(
+        &mut self,
+        user_id: u64,
+        limit: u32,
+        offset: u32,
+        order: FavoriteOrder,
+        direction: OrderDirection,
+    ) -> Result<ItemsPage<FavoriteItem<Mix>>> {
+        let url = self.api_url(
+            &format!("users/{}/favorites/mixes", user_id),
+            &[
+                ("limit", &limit.to_string()),
+                ("offset", &offset.to_string()),
+                ("order", order.as_str()),
+                ("orderDirection", direction.as_str()),
             ],
         );
         self.get(&url).await
     }
 
+    /// Every favorite track, draining the paged listing via
+    /// [`paginate_all`](super::client::paginate_all).
+    pub async fn favorite_tracks_all(&mut self, user_id: u64) -> Result<Vec<FavoriteItem<Track>>> {
+        let client = self.clone();
+        super::client::paginate_all(50, move |offset| {
+            let mut client = client.clone();
+            async move {
+                client
+                    .get_favorite_tracks(
+                        user_id,
+                        50,
+                        offset,
+                        FavoriteOrder::default(),
+                        OrderDirection::default(),
+                    )
+                    .await
+            }
+        })
+        .await
+    }
+
     pub async fn get_favorite_ids(&mut self, user_id: u64) -> Result<FavoriteIds> {
         let url = self.api_url(&format!("users/{}/favorites/ids", user_id), &[]);
         self.get(&url).await
     }
 
+    /// Compare a local "liked tracks" list against the account's favorites
+    /// and return what a two-way sync needs to reconcile them. Built on the
+    /// cheap id-only favorites fetch; both sides keep their original order,
+    /// so the caller can feed the halves straight to batch add/remove.
+    pub async fn diff_favorites(
+        &mut self,
+        user_id: u64,
+        local_ids: &[u64],
+    ) -> Result<FavoriteDiff> {
+        let remote = self
+            .get_favorite_ids(user_id)
+            .await?
+            .tracks
+            .unwrap_or_default();
+        let remote_set: HashSet<u64> = remote.iter().copied().collect();
+        let local_set: HashSet<u64> = local_ids.iter().copied().collect();
+
+        Ok(FavoriteDiff {
+            to_add: local_ids
+                .iter()
+                .copied()
+                .filter(|id| !remote_set.contains(id))
+                .collect(),
+            to_remove: remote
+                .into_iter()
+                .filter(|id| !local_set.contains(id))
+                .collect(),
+        })
+    }
+
     pub async fn add_favorite_track(&mut self, user_id: u64, track_id: u64) -> Result<()> {
         let url = self.api_url(
             &format!("users/{}/favorites/tracks", user_id),
             &[("trackIds", &track_id.to_string())],
         );
-        self.post_empty(&url, None).await
+        ignore_conflict(self.post_empty(&url, None).await)
     }
 
     pub async fn add_favorite_album(&mut self, user_id: u64, album_id: u64) -> Result<()> {
@@ -120,7 +220,7 @@ impl TidalClient {
             &format!("users/{}/favorites/albums", user_id),
             &[("albumIds", &album_id.to_string())],
         );
-        self.post_empty(&url, None).await
+        ignore_conflict(self.post_empty(&url, None).await)
     }
 
     pub async fn add_favorite_artist(&mut self, user_id: u64, artist_id: u64) -> Result<()> {
@@ -128,7 +228,7 @@ impl TidalClient {
             &format!("users/{}/favorites/artists", user_id),
             &[("artistIds", &artist_id.to_string())],
         );
-        self.post_empty(&url, None).await
+        ignore_conflict(self.post_empty(&url, None).await)
     }
 
     pub async fn add_favorite_playlist(&mut self, user_id: u64, playlist_id: &str) -> Result<()> {
@@ -136,7 +236,7 @@ impl TidalClient {
             &format!("users/{}/favorites/playlists", user_id),
             &[("uuids", playlist_id)],
         );
-        self.post_empty(&url, None).await
+        ignore_conflict(self.post_empty(&url, None).await)
     }
 
     pub async fn add_favorite_video(&mut self, user_id: u64, video_id: u64) -> Result<()> {
@@ -144,7 +244,15 @@ impl TidalClient {
             &format!("users/{}/favorites/videos", user_id),
             &[("videoIds", &video_id.to_string())],
         );
-        self.post_empty(&url, None).await
+        ignore_conflict(self.post_empty(&url, None).await)
+    }
+
+    pub async fn add_favorite_mix(&mut self, user_id: u64, mix_id: &str) -> Result<()> {
+        let url = self.api_url(
+            &format!("users/{}/favorites/mixes", user_id),
+            &[("mixIds", mix_id)],
+        );
+        ignore_conflict(self.post_empty(&url, None).await)
     }
 
     pub async fn remove_favorite_track(&mut self, user_id: u64, track_id: u64) -> Result<()> {
@@ -183,6 +291,14 @@ impl TidalClient {
         self.delete_empty(&url).await
     }
 
+    pub async fn remove_favorite_mix(&mut self, user_id: u64, mix_id: &str) -> Result<()> {
+        let url = self.api_url(
+            &format!("users/{}/favorites/mixes/{}", user_id, mix_id),
+            &[],
+        );
+        self.delete_empty(&url).await
+    }
+
     pub async fn remove_favorite_video(&mut self, user_id: u64, video_id: u64) -> Result<()> {
         let url = self.api_url(
             &format!("users/{}/favorites/videos/{}", user_id, video_id),
@@ -191,3 +307,23 @@ impl TidalClient {
         self.delete_empty(&url).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conflict_on_add_is_treated_as_success() {
+        let conflict = Err(TidalError::Api {
+            status: 409,
+            message: "already exists".into(),
+        });
+        assert!(ignore_conflict(conflict).is_ok());
+
+        let forbidden: Result<()> = Err(TidalError::Api {
+            status: 403,
+            message: "nope".into(),
+        });
+        assert!(ignore_conflict(forbidden).is_err());
+    }
+}
@@ -12,6 +12,13 @@ mod users;
 
 pub use client::{
     ClientConfig,
+    RateLimiter,
     TidalClient,
+    TokenRefreshCallback,
+    paginate_all,
 };
 pub use models::*;
+pub use playback::{
+    parse_hls_master,
+    parse_mpd,
+};
@@ -4,10 +4,52 @@ use serde::{
 };
 
 pub const IMAGE_BASE: &str = "https://resources.tidal.com/images";
+pub const VIDEO_BASE: &str = "https://resources.tidal.com/videos";
+
+/// `H:MM:SS` once a duration reaches an hour, `M:SS` below it.
+fn format_duration_hms(d: u32) -> String {
+    let hours = d / 3600;
+    let mins = (d % 3600) / 60;
+    let secs = d % 60;
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, mins, secs)
+    } else {
+        format!("{}:{:02}", mins, secs)
+    }
+}
 
 pub fn image_url(uuid: &str, size: ImageSize) -> String {
+    image_url_with_format(uuid, size, ImageFormat::Jpg)
+}
+
+/// Like [`image_url`], but with an explicit format. The resource host serves
+/// the same images as webp, which is noticeably smaller at equal quality;
+/// JPEG stays the default for compatibility.
+pub fn image_url_with_format(uuid: &str, size: ImageSize, format: ImageFormat) -> String {
     let path = uuid.replace('-', "/");
-    format!("{}/{}/{}.jpg", IMAGE_BASE, path, size.as_str())
+    format!(
+        "{}/{}/{}.{}",
+        IMAGE_BASE,
+        path,
+        size.as_str(),
+        format.extension()
+    )
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum ImageFormat {
+    #[default]
+    Jpg,
+    Webp,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Jpg => "jpg",
+            ImageFormat::Webp => "webp",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -37,6 +79,83 @@ pub struct SessionInfo {
     pub country_code: String,
 }
 
+/// How the client intends to use the stream, passed to the playback-info
+/// endpoint as `playbackmode`.
+///
+/// `Offline` is the mode the official apps use for downloads; it can return
+/// different (sometimes higher) quality or different DRM than `Stream`, but
+/// it is an entitlement: Tidal only grants it to subscriptions that include
+/// offline playback and may reject client identities it doesn't recognize as
+/// download-capable.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum PlaybackMode {
+    #[default]
+    Stream,
+    Offline,
+}
+
+impl PlaybackMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlaybackMode::Stream => "STREAM",
+            PlaybackMode::Offline => "OFFLINE",
+        }
+    }
+}
+
+/// One logged-in device from the sessions listing; see
+/// `TidalClient::get_active_sessions`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDevice {
+    pub session_id: Option<String>,
+    pub client: Option<SessionClient>,
+    pub country_code: Option<String>,
+    pub last_updated: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionClient {
+    pub id: Option<u64>,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub client_type: Option<String>,
+}
+
+/// A public profile summary from the v2 social graph (followers/following
+/// listings). Only the fields the endpoint reliably returns are modeled.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SocialProfile {
+    /// Tidal resource name, e.g. `trn:user:12345`.
+    pub trn: Option<String>,
+    pub profile_type: Option<String>,
+    pub name: Option<String>,
+    pub im_following: Option<bool>,
+    pub blocked: Option<bool>,
+}
+
+impl SocialProfile {
+    /// The numeric user id parsed out of [`trn`](Self::trn).
+    pub fn user_id(&self) -> Option<u64> {
+        self.trn
+            .as_deref()?
+            .rsplit(':')
+            .next()?
+            .parse()
+            .ok()
+    }
+}
+
+/// A cursor-paged listing from the v2 social endpoints. Pass `cursor` back to
+/// the same call to fetch the next page; `None` means the listing is done.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SocialPage {
+    pub items: Vec<SocialProfile>,
+    pub cursor: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct UserProfile {
     pub id: u64,
@@ -88,10 +207,18 @@ pub struct Artist {
 
 impl Artist {
     pub fn picture_url(&self, size: ImageSize) -> Option<String> {
+        self.picture_url_with_format(size, ImageFormat::Jpg)
+    }
+
+    pub fn picture_url_with_format(
+        &self,
+        size: ImageSize,
+        format: ImageFormat,
+    ) -> Option<String> {
         self.picture
             .as_ref()
             .or(self.selected_album_cover_fallback.as_ref())
-            .map(|uuid| image_url(uuid, size))
+            .map(|uuid| image_url_with_format(uuid, size, format))
     }
 }
 
@@ -117,6 +244,34 @@ pub struct ArtistBio {
     pub last_updated: Option<String>,
 }
 
+impl ArtistBio {
+    /// The bio `text` with Tidal's inline `wimpLink` markup stripped.
+    ///
+    /// Bios embed cross-references as
+    /// `[wimpLink artistId="123"]Name[/wimpLink]` (also `albumId`), which
+    /// renders as noise anywhere that isn't Tidal's own client. This keeps
+    /// the linked name and drops the surrounding tags; text without markup
+    /// passes through unchanged. Returns `None` when there is no bio text.
+    pub fn plain_text(&self) -> Option<String> {
+        let text = self.text.as_deref()?;
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("[wimpLink") {
+            out.push_str(&rest[..start]);
+            match rest[start..].find(']') {
+                Some(close) => rest = &rest[start + close + 1..],
+                None => {
+                    // Unterminated tag: keep the raw text rather than eat it.
+                    out.push_str(&rest[start..]);
+                    rest = "";
+                }
+            }
+        }
+        out.push_str(rest);
+        Some(out.replace("[/wimpLink]", ""))
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ArtistLink {
     pub url: String,
@@ -182,18 +337,72 @@ impl Album {
     }
 
     pub fn total_duration_formatted(&self) -> Option<String> {
-        self.duration.map(|d| {
-            let mins = d / 60;
-            let secs = d % 60;
-            format!("{}:{:02}", mins, secs)
-        })
+        self.duration.map(format_duration_hms)
+    }
+
+    /// Whether the album can actually be streamed with a regular
+    /// subscription: ready, streaming allowed, and not purchase-gated
+    /// (`payToStream`). Missing flags count as permissive, since the API
+    /// omits them for ordinary albums.
+    pub fn is_streamable(&self) -> bool {
+        self.stream_ready.unwrap_or(true)
+            && self.allow_streaming.unwrap_or(true)
+            && !self.pay_to_stream.unwrap_or(false)
     }
 
     pub fn cover_url(&self, size: ImageSize) -> Option<String> {
-        self.cover.as_ref().map(|uuid| image_url(uuid, size))
+        self.cover_url_with_format(size, ImageFormat::Jpg)
+    }
+
+    pub fn cover_url_with_format(&self, size: ImageSize, format: ImageFormat) -> Option<String> {
+        self.cover
+            .as_ref()
+            .map(|uuid| image_url_with_format(uuid, size, format))
+    }
+
+    /// The animated cover (an MP4 loop), when the album has one. Served from
+    /// the video resource host at the same square dimensions as stills.
+    pub fn video_cover_url(&self, size: ImageSize) -> Option<String> {
+        self.video_cover.as_ref().map(|uuid| {
+            let path = uuid.replace('-', "/");
+            format!("{}/{}/{}.mp4", VIDEO_BASE, path, size.as_str())
+        })
+    }
+
+    /// The release year, from `release_date` with `stream_start_date` as a
+    /// fallback.
+    pub fn release_year(&self) -> Option<u32> {
+        parse_year(
+            self.release_date
+                .as_deref()
+                .or(self.stream_start_date.as_deref())?,
+        )
+    }
+
+    /// The fully parsed release date (same fallback as `release_year`).
+    /// Requires the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn release_date_parsed(&self) -> Option<chrono::NaiveDate> {
+        parse_date(
+            self.release_date
+                .as_deref()
+                .or(self.stream_start_date.as_deref())?,
+        )
     }
 }
 
+/// Tidal dates are either plain `YYYY-MM-DD` or full RFC 3339 timestamps;
+/// both start with the four-digit year.
+pub(crate) fn parse_year(date: &str) -> Option<u32> {
+    date.split('-').next()?.parse().ok()
+}
+
+#[cfg(feature = "chrono")]
+pub(crate) fn parse_date(date: &str) -> Option<chrono::NaiveDate> {
+    let date_only = date.split('T').next().unwrap_or(date);
+    chrono::NaiveDate::parse_from_str(date_only, "%Y-%m-%d").ok()
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct AlbumReview {
     pub text: Option<String>,
@@ -229,7 +438,11 @@ pub struct Track {
     pub replay_gain: Option<f32>,
     pub peak: Option<f32>,
     pub url: Option<String>,
+    /// Popularity rounded to an integer on a 0–100 scale.
     pub popularity: Option<u32>,
+    /// The same popularity before rounding. Tidal exposes no play-count or
+    /// trend history; this float is the highest-precision signal available,
+    /// so charts should prefer it over `popularity` when present.
     #[serde(rename = "doublePopularity")]
     pub double_popularity: Option<f64>,
     pub bpm: Option<u32>,
@@ -279,9 +492,7 @@ impl Track {
     }
 
     pub fn duration_formatted(&self) -> String {
-        let mins = self.duration / 60;
-        let secs = self.duration % 60;
-        format!("{}:{:02}", mins, secs)
+        format_duration_hms(self.duration)
     }
 
     pub fn cover_url(&self, size: ImageSize) -> Option<String> {
@@ -306,6 +517,83 @@ impl Track {
             }
         })
     }
+
+    /// The title normalized for cross-service matching: the `version` suffix,
+    /// "(Remastered 2019)"-style parentheticals, " - Single Version"-style
+    /// dash suffixes and "(feat. …)" credits are stripped. The raw `title`
+    /// and `version` fields are left untouched.
+    pub fn normalized_title(&self) -> String {
+        let mut title = self.title.trim().to_string();
+
+        // The version field often duplicates a suffix embedded in the title.
+        if let Some(version) = self.version.as_deref().map(str::trim)
+            && !version.is_empty() {
+                for wrapped in [format!("({})", version), format!("- {}", version)] {
+                    if let Some(stripped) = title.strip_suffix(&wrapped) {
+                        title = stripped.trim_end().to_string();
+                    }
+                }
+            }
+
+        loop {
+            let stripped = strip_version_suffix(&title);
+            if stripped == title {
+                break;
+            }
+            title = stripped;
+        }
+
+        title
+    }
+}
+
+/// Whether a title suffix looks like version/release noise rather than part
+/// of the actual name.
+fn is_version_noise(text: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "remaster",
+        "version",
+        "edit",
+        "mix",
+        "mono",
+        "stereo",
+        "live",
+        "feat.",
+        "featuring",
+        "single",
+        "radio",
+        "deluxe",
+        "anniversary",
+        "demo",
+        "bonus",
+        "acoustic",
+        "instrumental",
+        "explicit",
+    ];
+    let lower = text.to_lowercase();
+    MARKERS.iter().any(|m| lower.contains(m))
+}
+
+/// Remove one trailing "(… noise …)" or " - … noise …" suffix, if present.
+fn strip_version_suffix(title: &str) -> String {
+    let title = title.trim_end();
+
+    if title.ends_with(')')
+        && let Some(open) = title.rfind('(') {
+            let inner = &title[open + 1..title.len() - 1];
+            if is_version_noise(inner) {
+                return title[..open].trim_end().to_string();
+            }
+        }
+
+    if let Some(pos) = title.rfind(" - ") {
+        let suffix = &title[pos + 3..];
+        if is_version_noise(suffix) {
+            return title[..pos].trim_end().to_string();
+        }
+    }
+
+    title.to_string()
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -335,9 +623,7 @@ impl Video {
     }
 
     pub fn duration_formatted(&self) -> String {
-        let mins = self.duration / 60;
-        let secs = self.duration % 60;
-        format!("{}:{:02}", mins, secs)
+        format_duration_hms(self.duration)
     }
 
     pub fn cover_url(&self, size: ImageSize) -> Option<String> {
@@ -345,7 +631,7 @@ impl Video {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Playlist {
     pub uuid: String,
     pub title: String,
@@ -372,22 +658,51 @@ pub struct Playlist {
 
 impl Playlist {
     pub fn total_duration_formatted(&self) -> Option<String> {
-        self.duration.map(|d| {
-            let mins = d / 60;
-            let secs = d % 60;
-            format!("{}:{:02}", mins, secs)
-        })
+        self.duration.map(format_duration_hms)
+    }
+
+    /// Total duration as `H:MM:SS` (or `M:SS` under an hour). Prefer this
+    /// over `total_duration_formatted` for playlists, which routinely run
+    /// past an hour and would otherwise display as e.g. "183:45".
+    pub fn duration_hms(&self) -> Option<String> {
+        self.duration.map(format_duration_hms)
     }
 
+    /// Prefers the square cover; see `square_image_url`/`wide_image_url` when
+    /// a specific shape is required.
     pub fn image_url(&self, size: ImageSize) -> Option<String> {
         self.square_image
             .as_ref()
             .or(self.image.as_ref())
             .map(|uuid| image_url(uuid, size))
     }
+
+    /// The square cover, when the playlist has one.
+    pub fn square_image_url(&self, size: ImageSize) -> Option<String> {
+        self.square_image.as_ref().map(|uuid| image_url(uuid, size))
+    }
+
+    /// The rectangular cover, typically set on video-oriented playlists.
+    pub fn wide_image_url(&self, size: ImageSize) -> Option<String> {
+        self.image.as_ref().map(|uuid| {
+            let path = uuid.replace('-', "/");
+            format!("{}/{}/{}.jpg", IMAGE_BASE, path, wide_dimensions(size))
+        })
+    }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Rectangular equivalents of the square [`ImageSize`] dimensions, used by
+/// the `image` field of video-oriented playlists.
+fn wide_dimensions(size: ImageSize) -> &'static str {
+    match size {
+        ImageSize::Small => "160x107",
+        ImageSize::Medium => "480x320",
+        ImageSize::Large => "750x500",
+        ImageSize::XLarge => "1080x720",
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PlaylistCreator {
     pub id: Option<u64>,
     pub name: Option<String>,
@@ -402,6 +717,58 @@ pub struct PlaylistItem {
     pub date_added: Option<String>,
 }
 
+/// One playlist entry with its media type preserved. [`PlaylistItem`]
+/// deserializes every entry as a [`Track`], which mangles video entries; a
+/// faithful playlist mirror should fetch entries via
+/// `TidalClient::get_playlist_entries` instead.
+#[derive(Debug, Clone)]
+pub struct PlaylistEntry {
+    pub item: PlaylistEntryItem,
+    pub date_added: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum PlaylistEntryItem {
+    Track(Box<Track>),
+    Video(Box<Video>),
+}
+
+impl<'de> Deserialize<'de> for PlaylistEntry {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Raw {
+            item: serde_json::Value,
+            #[serde(rename = "type")]
+            item_type: Option<String>,
+            #[serde(rename = "dateAdded")]
+            date_added: Option<String>,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        let is_video = raw
+            .item_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("video"));
+        let item = if is_video {
+            PlaylistEntryItem::Video(Box::new(
+                serde_json::from_value(raw.item).map_err(serde::de::Error::custom)?,
+            ))
+        } else {
+            PlaylistEntryItem::Track(Box::new(
+                serde_json::from_value(raw.item).map_err(serde::de::Error::custom)?,
+            ))
+        };
+
+        Ok(PlaylistEntry {
+            item,
+            date_added: raw.date_added,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Mix {
     pub id: String,
@@ -410,6 +777,37 @@ pub struct Mix {
     pub sub_title: Option<String>,
     #[serde(rename = "mixType")]
     pub mix_type: Option<String>,
+    pub images: Option<MixImages>,
+}
+
+impl Mix {
+    /// The largest available mix image, falling back to smaller sizes.
+    pub fn image_url(&self) -> Option<&str> {
+        let images = self.images.as_ref()?;
+        images
+            .large
+            .as_ref()
+            .or(images.medium.as_ref())
+            .or(images.small.as_ref())
+            .map(|i| i.url.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MixImages {
+    #[serde(rename = "SMALL")]
+    pub small: Option<MixImage>,
+    #[serde(rename = "MEDIUM")]
+    pub medium: Option<MixImage>,
+    #[serde(rename = "LARGE")]
+    pub large: Option<MixImage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MixImage {
+    pub url: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -452,6 +850,41 @@ pub struct FavoriteItem<T> {
     pub created: Option<String>,
 }
 
+/// Sort key for the favorites listing endpoints.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum FavoriteOrder {
+    #[default]
+    Date,
+    Name,
+    Artist,
+}
+
+impl FavoriteOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FavoriteOrder::Date => "DATE",
+            FavoriteOrder::Name => "NAME",
+            FavoriteOrder::Artist => "ARTIST",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum OrderDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl OrderDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderDirection::Asc => "ASC",
+            OrderDirection::Desc => "DESC",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct FavoriteIds {
     #[serde(rename = "TRACK")]
@@ -464,6 +897,32 @@ pub struct FavoriteIds {
     pub albums: Option<Vec<u64>>,
     #[serde(rename = "PLAYLIST")]
     pub playlists: Option<Vec<String>>,
+    #[serde(rename = "MIX")]
+    pub mixes: Option<Vec<String>>,
+}
+
+/// The two halves of a favorites sync, produced by
+/// `TidalClient::diff_favorites`: track ids liked locally but not on Tidal
+/// (feed to the batch add path) and ids favorited on Tidal but absent from
+/// the local list (feed to remove).
+#[derive(Debug, Clone, Default)]
+pub struct FavoriteDiff {
+    pub to_add: Vec<u64>,
+    pub to_remove: Vec<u64>,
+}
+
+/// One instrument stem of a track, for DJ/remix use. Only populated once
+/// Tidal exposes a stems playback path; see [`TidalClient::get_stems`].
+///
+/// [`TidalClient::get_stems`]: crate::TidalClient::get_stems
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StemStream {
+    /// Stem name, e.g. "vocals", "drums", "bass".
+    pub name: String,
+    pub urls: Vec<String>,
+    #[serde(rename = "mimeType")]
+    pub mime_type: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -491,6 +950,19 @@ pub struct PlaybackInfo {
     pub track_peak_amplitude: Option<f32>,
 }
 
+/// What `videos/{id}/playbackinfopostpaywall/v4` returns; the manifest is a
+/// base64-encoded HLS master playlist for unprotected streams.
+#[derive(Debug, Deserialize)]
+pub struct VideoPlaybackInfo {
+    #[serde(rename = "videoId")]
+    pub video_id: u64,
+    #[serde(rename = "videoQuality")]
+    pub video_quality: String,
+    #[serde(rename = "manifestMimeType")]
+    pub manifest_mime_type: String,
+    pub manifest: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct BtsManifest {
     #[serde(rename = "mimeType")]
@@ -546,6 +1018,21 @@ pub struct ItemsPage<T> {
     pub offset: Option<u32>,
 }
 
+/// Tidal caps the number of ids a single batch request may carry; longer id
+/// lists come back as a 400 or 414. Chunked fetches stay under the cap.
+pub(crate) const BATCH_IDS_LIMIT: usize = 100;
+
+/// Outcome of a chunked partial batch fetch such as
+/// `TidalClient::get_tracks_partial`: the items that resolved, in the order
+/// their ids were requested, plus the ids that did not resolve and why. Ids
+/// whose chunk request failed carry the request error; ids the API silently
+/// omitted are reported as not found.
+#[derive(Debug)]
+pub struct BatchOutcome<T> {
+    pub items: Vec<T>,
+    pub failed: Vec<(u64, String)>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Lyrics {
     #[serde(rename = "trackId")]
@@ -759,6 +1246,36 @@ pub struct AlbumPageModule {
     pub list_format: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArtistPage {
+    #[serde(rename = "selfLink")]
+    pub self_link: Option<String>,
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub rows: Vec<ArtistPageRow>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArtistPageRow {
+    pub modules: Vec<ArtistPageModule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArtistPageModule {
+    pub id: Option<String>,
+    #[serde(rename = "type")]
+    pub module_type: String,
+    pub width: Option<u32>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub artist: Option<Artist>,
+    pub bio: Option<ArtistBio>,
+    #[serde(rename = "pagedList")]
+    pub paged_list: Option<PagedList>,
+    #[serde(rename = "listFormat")]
+    pub list_format: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct PagedList {
     #[serde(rename = "dataApiPath")]
@@ -777,6 +1294,112 @@ pub struct PagedListItem {
     pub item_type: Option<String>,
 }
 
+/// A dynamic editorial page (`/pages/home`, `/pages/explore`,
+/// `/pages/genre_*`): the same rows-of-modules structure as [`AlbumPage`] /
+/// [`ArtistPage`], but with mixed content types per module. Items arrive as
+/// raw JSON; use the typed accessors on [`PageModule`] to pull out the
+/// content a module's `type` declares.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Page {
+    #[serde(rename = "selfLink")]
+    pub self_link: Option<String>,
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub rows: Vec<PageRow>,
+}
+
+impl Page {
+    /// Flatten the row structure into the modules in display order.
+    pub fn modules(&self) -> impl Iterator<Item = &PageModule> {
+        self.rows.iter().flat_map(|row| row.modules.iter())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PageRow {
+    pub modules: Vec<PageModule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PageModule {
+    pub id: Option<String>,
+    #[serde(rename = "type")]
+    pub module_type: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "pagedList")]
+    pub paged_list: Option<PageItemList>,
+    #[serde(rename = "listFormat")]
+    pub list_format: Option<String>,
+}
+
+/// Like [`PagedList`] but content-agnostic: editorial modules mix playlists,
+/// albums, tracks and mixes, so items stay raw JSON until a typed accessor
+/// interprets them.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PageItemList {
+    #[serde(rename = "dataApiPath")]
+    pub data_api_path: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[serde(rename = "totalNumberOfItems")]
+    pub total_number_of_items: Option<u32>,
+    pub items: Vec<serde_json::Value>,
+}
+
+impl PageModule {
+    /// Deserialize this module's items as `T`, accepting both bare objects
+    /// and the `{"item": ..., "type": ...}` wrapper some list modules use.
+    /// Items that fit neither shape are skipped.
+    fn items_as<T: serde::de::DeserializeOwned>(&self) -> Vec<T> {
+        let Some(list) = &self.paged_list else {
+            return Vec::new();
+        };
+        list.items
+            .iter()
+            .filter_map(|value| {
+                serde_json::from_value(value.clone()).ok().or_else(|| {
+                    value
+                        .get("item")
+                        .and_then(|inner| serde_json::from_value(inner.clone()).ok())
+                })
+            })
+            .collect()
+    }
+
+    pub fn playlists(&self) -> Vec<Playlist> {
+        if self.module_type == "PLAYLIST_LIST" {
+            self.items_as()
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub fn albums(&self) -> Vec<Album> {
+        if self.module_type == "ALBUM_LIST" {
+            self.items_as()
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub fn tracks(&self) -> Vec<Track> {
+        if self.module_type == "TRACK_LIST" {
+            self.items_as()
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub fn mixes(&self) -> Vec<Mix> {
+        if self.module_type == "MIX_LIST" || self.module_type == "MIXED_TYPES_LIST" {
+            self.items_as()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct AlbumItemsCreditsResponse {
     pub limit: u32,
@@ -785,3 +1408,187 @@ pub struct AlbumItemsCreditsResponse {
     pub total_number_of_items: u32,
     pub items: Vec<TrackCredits>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_modules_expose_typed_items() {
+        let page: Page = serde_json::from_value(serde_json::json!({
+            "title": "Home",
+            "rows": [
+                {"modules": [{
+                    "type": "PLAYLIST_LIST",
+                    "title": "Featured",
+                    "pagedList": {"items": [
+                        {"uuid": "abc", "title": "Hits", "numberOfTracks": 10}
+                    ]}
+                }]},
+                {"modules": [{
+                    "type": "TRACK_LIST",
+                    "pagedList": {"items": [
+                        {"item": {
+                            "id": 7,
+                            "title": "Song",
+                            "duration": 200,
+                            "explicit": false,
+                            "artists": []
+                        }, "type": "track"}
+                    ]}
+                }]},
+                {"modules": [{
+                    "type": "MIX_LIST",
+                    "pagedList": {"items": [{"id": "mix1", "title": "My Mix"}]}
+                }]}
+            ]
+        }))
+        .unwrap();
+
+        let modules: Vec<_> = page.modules().collect();
+        assert_eq!(modules.len(), 3);
+        assert_eq!(modules[0].playlists()[0].title, "Hits");
+        assert!(modules[0].tracks().is_empty());
+        assert_eq!(modules[1].tracks()[0].id, 7);
+        assert_eq!(modules[2].mixes()[0].id, "mix1");
+    }
+
+    #[test]
+    fn artist_bio_plain_text_strips_wimp_links() {
+        let bio = ArtistBio {
+            source: None,
+            text: Some(
+                "Formed with [wimpLink artistId=\"42\"]Someone[/wimpLink] \
+                 after [wimpLink albumId=\"7\"]That Album[/wimpLink]."
+                    .to_string(),
+            ),
+            summary: None,
+            last_updated: None,
+        };
+        assert_eq!(
+            bio.plain_text().unwrap(),
+            "Formed with Someone after That Album."
+        );
+
+        let plain = ArtistBio {
+            source: None,
+            text: Some("No markup here.".to_string()),
+            summary: None,
+            last_updated: None,
+        };
+        assert_eq!(plain.plain_text().unwrap(), "No markup here.");
+    }
+
+    #[test]
+    fn playback_info_deserializes_low_quality_shape() {
+        // LOW streams omit bit depth and sample rate entirely.
+        let json = r#"{
+            "trackId": 12345,
+            "audioQuality": "LOW",
+            "audioMode": "STEREO",
+            "manifestMimeType": "application/vnd.tidal.bts",
+            "manifest": "e30="
+        }"#;
+
+        let info: PlaybackInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(info.track_id, 12345);
+        assert_eq!(info.audio_quality, "LOW");
+        assert!(info.bit_depth.is_none());
+        assert!(info.sample_rate.is_none());
+    }
+
+    fn track_with(title: &str, version: Option<&str>) -> Track {
+        let mut value = serde_json::json!({
+            "id": 1,
+            "title": title,
+            "duration": 200,
+            "explicit": false,
+            "artists": [],
+        });
+        if let Some(v) = version {
+            value["version"] = serde_json::json!(v);
+        }
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn normalized_title_strips_version_noise() {
+        let cases = [
+            ("Come As You Are (Remastered 2019)", "Come As You Are"),
+            ("Lithium - Single Version", "Lithium"),
+            ("Breed (feat. Someone)", "Breed"),
+            ("Polly (Live) (Remastered)", "Polly"),
+            ("In Bloom", "In Bloom"),
+            // Parenthetical that's part of the name, not noise.
+            ("(Don't Fear) The Reaper", "(Don't Fear) The Reaper"),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(track_with(raw, None).normalized_title(), expected);
+        }
+    }
+
+    #[test]
+    fn normalized_title_uses_version_field() {
+        let track = track_with("Heart-Shaped Box (2013 Mix)", Some("2013 Mix"));
+        assert_eq!(track.normalized_title(), "Heart-Shaped Box");
+        // Raw fields stay intact.
+        assert_eq!(track.title, "Heart-Shaped Box (2013 Mix)");
+    }
+
+    #[test]
+    fn playlist_entry_distinguishes_tracks_from_videos() {
+        let json = r#"{
+            "item": { "id": 7, "title": "Clip", "duration": 200, "explicit": false, "artists": [] },
+            "type": "video",
+            "dateAdded": "2024-01-02T03:04:05.000+0000"
+        }"#;
+        let entry: PlaylistEntry = serde_json::from_str(json).unwrap();
+        assert!(matches!(entry.item, PlaylistEntryItem::Video(ref v) if v.id == 7));
+        assert!(entry.date_added.is_some());
+
+        let json = r#"{
+            "item": { "id": 8, "title": "Song", "duration": 180, "explicit": false, "artists": [] },
+            "type": "track"
+        }"#;
+        let entry: PlaylistEntry = serde_json::from_str(json).unwrap();
+        assert!(matches!(entry.item, PlaylistEntryItem::Track(ref t) if t.id == 8));
+        assert!(entry.date_added.is_none());
+    }
+
+    #[test]
+    fn image_url_defaults_to_jpg_and_can_request_webp() {
+        let uuid = "aaaa-bbbb-cccc";
+        assert_eq!(
+            image_url(uuid, ImageSize::Medium),
+            "https://resources.tidal.com/images/aaaa/bbbb/cccc/320x320.jpg"
+        );
+        assert_eq!(
+            image_url_with_format(uuid, ImageSize::Medium, ImageFormat::Webp),
+            "https://resources.tidal.com/images/aaaa/bbbb/cccc/320x320.webp"
+        );
+    }
+
+    #[test]
+    fn duration_formatting_handles_the_hour_boundary() {
+        assert_eq!(format_duration_hms(0), "0:00");
+        assert_eq!(format_duration_hms(3599), "59:59");
+        assert_eq!(format_duration_hms(3600), "1:00:00");
+        assert_eq!(format_duration_hms(7623), "2:07:03");
+    }
+
+    #[test]
+    fn parse_year_handles_both_tidal_date_formats() {
+        assert_eq!(parse_year("2019-07-26"), Some(2019));
+        assert_eq!(parse_year("2019-07-26T00:00:00.000+0000"), Some(2019));
+        assert_eq!(parse_year("not a date"), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn parse_date_handles_both_tidal_date_formats() {
+        let expected = chrono::NaiveDate::from_ymd_opt(2019, 7, 26).unwrap();
+        assert_eq!(parse_date("2019-07-26"), Some(expected));
+        assert_eq!(parse_date("2019-07-26T00:00:00.000+0000"), Some(expected));
+        assert_eq!(parse_date("2019-13-01"), None);
+    }
+}
@@ -1,5 +1,10 @@
 use base64::Engine as _;
-use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::engine::general_purpose::{
+    STANDARD as BASE64,
+    STANDARD_NO_PAD as BASE64_NO_PAD,
+    URL_SAFE as BASE64_URL_SAFE,
+    URL_SAFE_NO_PAD as BASE64_URL_SAFE_NO_PAD,
+};
 use quick_xml::Reader;
 use quick_xml::events::Event;
 
@@ -8,43 +13,162 @@ use super::models::{
     BtsManifest,
     DashManifest,
     PlaybackInfo,
+    PlaybackMode,
+    StemStream,
+    VideoPlaybackInfo,
 };
 use crate::core::error::{
     Result,
     TidalError,
 };
+use crate::core::stream::{
+    HlsVariant,
+    VideoQuality,
+    VideoStreamInfo,
+};
 
 impl TidalClient {
+    /// Fetch playback info for a track. `mode` selects the `STREAM` vs
+    /// `OFFLINE` entitlement (see [`PlaybackMode`] for the implications);
+    /// `prefetch` marks the request as speculative pre-buffering, which the
+    /// backend may serve at reduced quality.
     pub async fn get_playback_info(
         &mut self,
         track_id: u64,
         quality: &str,
+        mode: PlaybackMode,
+        prefetch: bool,
     ) -> Result<PlaybackInfo> {
         let url = self.listen_url(
             &format!("tracks/{}/playbackinfopostpaywall/v4", track_id),
             &[
-                ("playbackmode", "STREAM"),
+                ("playbackmode", mode.as_str()),
                 ("assetpresentation", "FULL"),
                 ("audioquality", quality),
-                ("prefetch", "false"),
+                ("prefetch", if prefetch { "true" } else { "false" }),
             ],
         );
         self.get(&url).await
     }
 
+    /// Fetch the individual instrument stems for a track.
+    ///
+    /// Checks the track's `stem_ready` flag first and fails with a clear
+    /// message when stems aren't offered. Tidal has not published a stems
+    /// playback endpoint yet, so even stem-ready tracks currently return an
+    /// error rather than guessing at an undocumented URL; once the endpoint
+    /// exists this is where it plugs in.
+    pub async fn get_stems(&mut self, track_id: u64) -> Result<Vec<StemStream>> {
+        let track = self.get_track(track_id).await?;
+
+        if track.stem_ready != Some(true) {
+            return Err(TidalError::Manifest(format!(
+                "Stems not available for track {}",
+                track_id
+            )));
+        }
+
+        Err(TidalError::Manifest(
+            "Stem playback is not exposed by a public endpoint yet".into(),
+        ))
+    }
+
+    /// Fetch playback info for a video from
+    /// `videos/{id}/playbackinfopostpaywall/v4` — the video counterpart of
+    /// [`get_playback_info`](Self::get_playback_info).
+    pub async fn get_video_playback_info(
+        &mut self,
+        video_id: u64,
+        quality: VideoQuality,
+    ) -> Result<VideoPlaybackInfo> {
+        let url = self.listen_url(
+            &format!("videos/{}/playbackinfopostpaywall/v4", video_id),
+            &[
+                ("playbackmode", PlaybackMode::Stream.as_str()),
+                ("assetpresentation", "FULL"),
+                ("videoquality", quality.as_str()),
+            ],
+        );
+        self.get(&url).await
+    }
+
+    /// Fetch and parse a video's manifest into its HLS variant streams. DRM
+    /// wrapped (`vnd.tidal.emu`) videos fail with the same explanation as
+    /// protected audio: there's no segment list to extract without a DRM
+    /// client.
+    pub async fn get_video_stream_info(
+        &mut self,
+        video_id: u64,
+        quality: VideoQuality,
+    ) -> Result<VideoStreamInfo> {
+        let playback_info = self.get_video_playback_info(video_id, quality.clone()).await?;
+
+        match playback_info.manifest_mime_type.as_str() {
+            "application/vnd.apple.mpegurl" => {
+                let decoded = decode_manifest_base64(&playback_info.manifest)?;
+                let playlist = String::from_utf8(decoded)?;
+                Ok(VideoStreamInfo {
+                    video_id: playback_info.video_id,
+                    variants: parse_hls_master(&playlist)?,
+                    requested_quality: quality.as_str().to_string(),
+                    actual_quality: playback_info.video_quality,
+                })
+            }
+            "application/vnd.tidal.emu" => Err(TidalError::Manifest(format!(
+                "Video {} returned a DRM-protected EMU manifest; this content \
+                 can't be streamed without a DRM client. Try a lower quality, \
+                 which may come back as an unprotected manifest",
+                playback_info.video_id
+            ))),
+            other => Err(TidalError::Manifest(format!(
+                "Video {} returned an unknown manifest type \"{}\"; only HLS \
+                 (vnd.apple.mpegurl) manifests are supported",
+                playback_info.video_id, other
+            ))),
+        }
+    }
+
     pub fn decode_bts_manifest(&self, playback_info: &PlaybackInfo) -> Result<BtsManifest> {
-        let decoded = BASE64.decode(&playback_info.manifest)?;
+        let decoded = decode_manifest_base64(&playback_info.manifest)?;
         let manifest_str = String::from_utf8(decoded)?;
         Ok(serde_json::from_str(&manifest_str)?)
     }
 
     pub fn decode_dash_manifest(&self, playback_info: &PlaybackInfo) -> Result<DashManifest> {
-        let decoded = BASE64.decode(&playback_info.manifest)?;
+        let decoded = decode_manifest_base64(&playback_info.manifest)?;
         let manifest_str = String::from_utf8(decoded)?;
         parse_mpd(&manifest_str)
     }
 }
 
+/// Tidal usually base64-encodes the manifest with the standard alphabet, but
+/// some tracks come back URL-safe (and/or unpadded). Try each encoding before
+/// giving up so neither variant surfaces as a "Decode error".
+fn decode_manifest_base64(manifest: &str) -> Result<Vec<u8>> {
+    BASE64
+        .decode(manifest)
+        .or_else(|_| BASE64_NO_PAD.decode(manifest))
+        .or_else(|_| BASE64_URL_SAFE.decode(manifest))
+        .or_else(|_| BASE64_URL_SAFE_NO_PAD.decode(manifest))
+        .map_err(Into::into)
+}
+
+/// Expand the `$Number$` placeholder in a SegmentTemplate `media` attribute,
+/// including the printf-style padded form `$Number%0Nd$` some manifests use.
+fn expand_segment_number(template: &str, number: u64) -> String {
+    const PADDED_PREFIX: &str = "$Number%0";
+    if let Some(start) = template.find(PADDED_PREFIX) {
+        let after = &template[start + PADDED_PREFIX.len()..];
+        if let Some(d_pos) = after.find("d$")
+            && let Ok(width) = after[..d_pos].parse::<usize>()
+        {
+            let placeholder = &template[start..start + PADDED_PREFIX.len() + d_pos + 2];
+            return template.replace(placeholder, &format!("{:0width$}", number));
+        }
+    }
+    template.replace("$Number$", &number.to_string())
+}
+
 pub fn parse_mpd(mpd_string: &str) -> Result<DashManifest> {
     let mut reader = Reader::from_str(mpd_string);
     let mut urls: Vec<String> = Vec::new();
@@ -53,6 +177,7 @@ pub fn parse_mpd(mpd_string: &str) -> Result<DashManifest> {
     let mut in_segment_timeline = false;
     let mut initialization_url: Option<String> = None;
     let mut media_template: Option<String> = None;
+    let mut start_number = 1u64;
     let mut segment_durations: Vec<(u64, u32)> = Vec::new();
 
     loop {
@@ -86,6 +211,10 @@ pub fn parse_mpd(mpd_string: &str) -> Result<DashManifest> {
                                 media_template =
                                     Some(String::from_utf8_lossy(&attr.value).to_string());
                             }
+                            b"startNumber" => {
+                                start_number =
+                                    String::from_utf8_lossy(&attr.value).parse().unwrap_or(1);
+                            }
                             _ => {}
                         }
                     }
@@ -111,11 +240,10 @@ pub fn parse_mpd(mpd_string: &str) -> Result<DashManifest> {
                 }
                 _ => {}
             },
-            Ok(Event::End(ref e)) => {
-                if e.name().as_ref() == b"SegmentTimeline" {
+            Ok(Event::End(ref e))
+                if e.name().as_ref() == b"SegmentTimeline" => {
                     in_segment_timeline = false;
                 }
-            }
             Ok(Event::Eof) => break,
             Err(e) => return Err(TidalError::Xml(e.to_string())),
             _ => {}
@@ -127,11 +255,23 @@ pub fn parse_mpd(mpd_string: &str) -> Result<DashManifest> {
     }
 
     if let Some(media) = media_template {
-        let mut segment_number = 1u32;
-        for (_duration, count) in segment_durations {
-            for _ in 0..count {
-                urls.push(media.replace("$Number$", &segment_number.to_string()));
-                segment_number += 1;
+        if media.contains("$Time$") {
+            // $Time$ addressing: each URL carries the cumulative offset of
+            // the segment's start, accumulated from the timeline `d` values.
+            let mut time: u64 = 0;
+            for (duration, count) in segment_durations {
+                for _ in 0..count {
+                    urls.push(media.replace("$Time$", &time.to_string()));
+                    time += duration;
+                }
+            }
+        } else {
+            let mut segment_number = start_number;
+            for (_duration, count) in segment_durations {
+                for _ in 0..count {
+                    urls.push(expand_segment_number(&media, segment_number));
+                    segment_number += 1;
+                }
             }
         }
     }
@@ -152,3 +292,149 @@ pub fn parse_mpd(mpd_string: &str) -> Result<DashManifest> {
         urls,
     })
 }
+
+/// Parse an HLS master playlist into its variant streams, keeping playlist
+/// order. Attribute splitting is naive (on commas), which is fine for the
+/// unquoted `BANDWIDTH`/`RESOLUTION` attributes we read — quoted values like
+/// `CODECS="a,b"` merely produce fragments we ignore.
+pub fn parse_hls_master(playlist: &str) -> Result<Vec<HlsVariant>> {
+    if !playlist.trim_start().starts_with("#EXTM3U") {
+        return Err(TidalError::Manifest(
+            "Not an HLS playlist: missing #EXTM3U header".into(),
+        ));
+    }
+
+    let mut variants = Vec::new();
+    let mut pending: Option<(Option<u64>, Option<String>)> = None;
+
+    for line in playlist.lines() {
+        let line = line.trim();
+        if let Some(attrs) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+            let mut bandwidth = None;
+            let mut resolution = None;
+            for attr in attrs.split(',') {
+                if let Some(value) = attr.strip_prefix("BANDWIDTH=") {
+                    bandwidth = value.trim().parse().ok();
+                } else if let Some(value) = attr.strip_prefix("RESOLUTION=") {
+                    resolution = Some(value.trim().to_string());
+                }
+            }
+            pending = Some((bandwidth, resolution));
+        } else if !line.is_empty()
+            && !line.starts_with('#')
+            && let Some((bandwidth, resolution)) = pending.take()
+        {
+            // The URI line belonging to the preceding #EXT-X-STREAM-INF tag.
+            variants.push(HlsVariant {
+                url: line.to_string(),
+                bandwidth,
+                resolution,
+            });
+        }
+    }
+
+    if variants.is_empty() {
+        return Err(TidalError::Manifest(
+            "No variant streams found in HLS master playlist".into(),
+        ));
+    }
+
+    Ok(variants)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mpd_honors_start_number_and_padded_numbers() {
+        let mpd = r#"<?xml version="1.0" encoding="UTF-8"?>
+<MPD xmlns="urn:mpeg:dash:schema:mpd:2011" type="static">
+  <Period>
+    <AdaptationSet mimeType="audio/mp4">
+      <Representation codecs="flac">
+        <SegmentTemplate initialization="https://cdn.example.com/init.mp4"
+            media="https://cdn.example.com/seg_$Number%03d$.mp4" startNumber="0">
+          <SegmentTimeline>
+            <S d="4096" r="2"/>
+          </SegmentTimeline>
+        </SegmentTemplate>
+      </Representation>
+    </AdaptationSet>
+  </Period>
+</MPD>"#;
+
+        let manifest = parse_mpd(mpd).unwrap();
+        assert_eq!(
+            manifest.urls,
+            vec![
+                "https://cdn.example.com/init.mp4",
+                "https://cdn.example.com/seg_000.mp4",
+                "https://cdn.example.com/seg_001.mp4",
+                "https://cdn.example.com/seg_002.mp4",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_mpd_expands_time_templates_cumulatively() {
+        let mpd = r#"<?xml version="1.0" encoding="UTF-8"?>
+<MPD xmlns="urn:mpeg:dash:schema:mpd:2011" type="static">
+  <Period>
+    <AdaptationSet mimeType="audio/mp4">
+      <Representation codecs="mp4a.40.2">
+        <SegmentTemplate initialization="https://cdn.example.com/init.mp4"
+            media="https://cdn.example.com/seg_$Time$.mp4">
+          <SegmentTimeline>
+            <S d="4096" r="1"/>
+            <S d="2048"/>
+          </SegmentTimeline>
+        </SegmentTemplate>
+      </Representation>
+    </AdaptationSet>
+  </Period>
+</MPD>"#;
+
+        let manifest = parse_mpd(mpd).unwrap();
+        assert_eq!(
+            manifest.urls,
+            vec![
+                "https://cdn.example.com/init.mp4",
+                "https://cdn.example.com/seg_0.mp4",
+                "https://cdn.example.com/seg_4096.mp4",
+                "https://cdn.example.com/seg_8192.mp4",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_hls_master_reads_variants_in_order() {
+        let playlist = "#EXTM3U\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=800000,RESOLUTION=640x360,CODECS=\"avc1.64001f,mp4a.40.2\"\n\
+            https://cdn.example.com/360.m3u8\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=4000000,RESOLUTION=1920x1080\n\
+            https://cdn.example.com/1080.m3u8\n";
+
+        let variants = parse_hls_master(playlist).unwrap();
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[0].url, "https://cdn.example.com/360.m3u8");
+        assert_eq!(variants[0].bandwidth, Some(800_000));
+        assert_eq!(variants[0].resolution.as_deref(), Some("640x360"));
+        assert_eq!(variants[1].bandwidth, Some(4_000_000));
+
+        assert!(parse_hls_master("not a playlist").is_err());
+        assert!(parse_hls_master("#EXTM3U\n#EXT-X-VERSION:3\n").is_err());
+    }
+
+    #[test]
+    fn url_safe_manifest_base64_decodes() {
+        // '>' and '?' force '+'/'/' in standard base64 and '-'/'_' URL-safe.
+        let payload = b"{\"mimeType\":\"audio/flac\"}>>???";
+        let url_safe = BASE64_URL_SAFE_NO_PAD.encode(payload);
+        assert!(BASE64.decode(&url_safe).is_err());
+        assert_eq!(decode_manifest_base64(&url_safe).unwrap(), payload);
+
+        let standard = BASE64.encode(payload);
+        assert_eq!(decode_manifest_base64(&standard).unwrap(), payload);
+    }
+}
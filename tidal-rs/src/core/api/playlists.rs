@@ -2,11 +2,18 @@ use super::client::TidalClient;
 use super::models::{
     ItemsPage,
     Playlist,
+    PlaylistEntry,
     PlaylistItem,
 };
-use crate::core::error::Result;
+use crate::core::error::{
+    Result,
+    TidalError,
+};
 
 impl TidalClient {
+    /// The canonical cheap metadata fetch for a playlist uuid: one request
+    /// returning title, `duration`, `number_of_tracks` and so on, without
+    /// touching the item list.
     pub async fn get_playlist(&mut self, playlist_id: &str) -> Result<Playlist> {
         let url = self.api_url(&format!("playlists/{}", playlist_id), &[]);
         self.get(&url).await
@@ -28,6 +35,25 @@ impl TidalClient {
         self.get(&url).await
     }
 
+    /// Like [`get_playlist_tracks`](Self::get_playlist_tracks), but preserves
+    /// each entry's media type and date-added, so video entries survive a
+    /// backup/restore round trip instead of being misread as tracks.
+    pub async fn get_playlist_entries(
+        &mut self,
+        playlist_id: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<ItemsPage<PlaylistEntry>> {
+        let url = self.api_url(
+            &format!("playlists/{}/items", playlist_id),
+            &[
+                ("limit", &limit.to_string()),
+                ("offset", &offset.to_string()),
+            ],
+        );
+        self.get(&url).await
+    }
+
     pub async fn get_user_playlists(
         &mut self,
         user_id: u64,
@@ -39,11 +65,70 @@ impl TidalClient {
             &[
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
+                ("order", "DATE"),
+                ("orderDirection", "DESC"),
             ],
         );
         self.get(&url).await
     }
 
+    /// Fetch every playlist the user owns, paging until `ItemsPage.total` is
+    /// reached. `get_user_playlists` silently truncates for users with more
+    /// playlists than one page; library tools should use this instead.
+    pub async fn get_all_user_playlists(&mut self, user_id: u64) -> Result<Vec<Playlist>> {
+        let limit = 50u32;
+        let mut offset = 0u32;
+        let mut playlists = Vec::new();
+
+        loop {
+            let page = self.get_user_playlists(user_id, limit, offset).await?;
+            if page.items.is_empty() {
+                break;
+            }
+
+            playlists.extend(page.items);
+
+            if playlists.len() as u32 >= page.total {
+                break;
+            }
+            offset += limit;
+        }
+
+        Ok(playlists)
+    }
+
+    /// Fetch a playlist together with its `ETag` header, which identifies the
+    /// current revision of the playlist's contents.
+    pub async fn get_playlist_with_etag(
+        &mut self,
+        playlist_id: &str,
+    ) -> Result<(Playlist, Option<String>)> {
+        let url = self.api_url(&format!("playlists/{}", playlist_id), &[]);
+        self.get_with_etag(&url).await
+    }
+
+    /// Fetch a page of the user's playlists and resolve each one's `ETag`.
+    ///
+    /// `last_updated` on [`Playlist`] already flags most changes; the ETag is
+    /// what a differential sync should persist to decide whether a playlist's
+    /// item list needs re-fetching. This costs one extra request per playlist.
+    pub async fn get_user_playlists_with_etags(
+        &mut self,
+        user_id: u64,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<(Playlist, Option<String>)>> {
+        let page = self.get_user_playlists(user_id, limit, offset).await?;
+
+        let mut playlists = Vec::with_capacity(page.items.len());
+        for playlist in page.items {
+            let (_, etag) = self.get_playlist_with_etag(&playlist.uuid).await?;
+            playlists.push((playlist, etag));
+        }
+
+        Ok(playlists)
+    }
+
     pub async fn create_playlist(
         &mut self,
         user_id: u64,
@@ -72,8 +157,180 @@ impl TidalClient {
         self.post_empty(&url, None).await
     }
 
+    /// Move the item at `from_index` to `to_index` (both zero-based).
+    ///
+    /// Tidal's playlist mutation endpoints demand an `If-None-Match` header
+    /// carrying the playlist's current content revision — the `ETag`
+    /// response header that [`get_playlist_with_etag`](Self::get_playlist_with_etag)
+    /// exposes. This fetches a fresh ETag, issues the move, and on a 412
+    /// (someone else changed the playlist in between) re-fetches the ETag
+    /// and retries once before giving up.
+    pub async fn move_playlist_item(
+        &mut self,
+        playlist_id: &str,
+        from_index: u32,
+        to_index: u32,
+    ) -> Result<()> {
+        let url = self.api_url(
+            &format!("playlists/{}/items/{}", playlist_id, from_index),
+            &[("toIndex", &to_index.to_string())],
+        );
+
+        let (_, etag) = self.get_playlist_with_etag(playlist_id).await?;
+        match self.post_empty_with_etag(&url, None, etag.as_deref()).await {
+            Err(TidalError::Precondition(_)) => {
+                let (_, etag) = self.get_playlist_with_etag(playlist_id).await?;
+                self.post_empty_with_etag(&url, None, etag.as_deref()).await
+            }
+            result => result,
+        }
+    }
+
+    /// Remove the item at `index` (zero-based), with the same ETag guard and
+    /// stale-precondition retry as
+    /// [`move_playlist_item`](Self::move_playlist_item).
+    pub async fn remove_playlist_item(&mut self, playlist_id: &str, index: u32) -> Result<()> {
+        let url = self.api_url(&format!("playlists/{}/items/{}", playlist_id, index), &[]);
+
+        let (_, etag) = self.get_playlist_with_etag(playlist_id).await?;
+        match self.delete_empty_with_etag(&url, etag.as_deref()).await {
+            Err(TidalError::Precondition(_)) => {
+                let (_, etag) = self.get_playlist_with_etag(playlist_id).await?;
+                self.delete_empty_with_etag(&url, etag.as_deref()).await
+            }
+            result => result,
+        }
+    }
+
+    /// Remove tracks by id instead of by index, which is racy while the
+    /// playlist is being edited elsewhere: the current item list is fetched
+    /// first and ids resolved to their positions at that moment. Each id in
+    /// `track_ids` removes one occurrence (pass an id twice to remove two
+    /// copies of a duplicated track); removal runs highest index first so
+    /// earlier deletions don't shift the remaining targets. Every removal
+    /// carries the same ETag guard and stale-precondition retry as
+    /// [`remove_playlist_item`](Self::remove_playlist_item). Returns how
+    /// many items were actually removed; ids not present in the playlist
+    /// are skipped.
+    pub async fn remove_tracks_from_playlist(
+        &mut self,
+        playlist_id: &str,
+        track_ids: &[u64],
+    ) -> Result<usize> {
+        let limit = 100u32;
+        let mut items = Vec::new();
+        let mut offset = 0u32;
+        loop {
+            let page = self.get_playlist_tracks(playlist_id, limit, offset).await?;
+            let fetched = page.items.len();
+            items.extend(page.items.into_iter().map(|item| item.item.id));
+            if fetched < limit as usize || items.len() as u32 >= page.total {
+                break;
+            }
+            offset += limit;
+        }
+
+        let indices = removal_indices(&items, track_ids);
+        for &index in &indices {
+            self.remove_playlist_item(playlist_id, index).await?;
+        }
+        Ok(indices.len())
+    }
+
+    /// Update a playlist's title and/or description in place. `None` leaves
+    /// a field as it is. Carries the same ETag guard and stale-precondition
+    /// retry as [`move_playlist_item`](Self::move_playlist_item).
+    pub async fn update_playlist(
+        &mut self,
+        playlist_id: &str,
+        title: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<()> {
+        if title.is_none() && description.is_none() {
+            return Ok(());
+        }
+
+        let mut form = Vec::new();
+        if let Some(title) = title {
+            form.push(("title", title));
+        }
+        if let Some(description) = description {
+            form.push(("description", description));
+        }
+        // The fields go in a form-encoded body, not the query string — the
+        // endpoint ignores query parameters.
+        let url = self.api_url(&format!("playlists/{}", playlist_id), &[]);
+
+        let (_, etag) = self.get_playlist_with_etag(playlist_id).await?;
+        match self.post_form_with_etag(&url, &form, etag.as_deref()).await {
+            Err(TidalError::Precondition(_)) => {
+                let (_, etag) = self.get_playlist_with_etag(playlist_id).await?;
+                self.post_form_with_etag(&url, &form, etag.as_deref()).await
+            }
+            result => result,
+        }
+    }
+
+    /// Toggle a playlist between public and private, with the usual ETag
+    /// guard. The visibility endpoints are verbs of their own rather than
+    /// fields on the update call.
+    pub async fn set_playlist_public(&mut self, playlist_id: &str, public: bool) -> Result<()> {
+        let action = if public { "public" } else { "private" };
+        let url = self.api_url(&format!("playlists/{}/{}", playlist_id, action), &[]);
+
+        let (_, etag) = self.get_playlist_with_etag(playlist_id).await?;
+        match self.post_empty_with_etag(&url, None, etag.as_deref()).await {
+            Err(TidalError::Precondition(_)) => {
+                let (_, etag) = self.get_playlist_with_etag(playlist_id).await?;
+                self.post_empty_with_etag(&url, None, etag.as_deref()).await
+            }
+            result => result,
+        }
+    }
+
     pub async fn delete_playlist(&mut self, playlist_id: &str) -> Result<()> {
         let url = self.api_url(&format!("playlists/{}", playlist_id), &[]);
         self.delete_empty(&url).await
     }
 }
+
+/// Resolve requested track ids to the playlist indices to delete, highest
+/// first. Each requested id consumes one occurrence, scanning front to back,
+/// so a duplicated track loses only as many copies as the caller asked for.
+fn removal_indices(items: &[u64], track_ids: &[u64]) -> Vec<u32> {
+    let mut taken = vec![false; items.len()];
+    let mut indices = Vec::new();
+
+    for &track_id in track_ids {
+        if let Some(index) = items
+            .iter()
+            .enumerate()
+            .position(|(i, &id)| id == track_id && !taken[i])
+        {
+            taken[index] = true;
+            indices.push(index as u32);
+        }
+    }
+
+    indices.sort_unstable_by(|a, b| b.cmp(a));
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removal_indices_take_one_occurrence_per_requested_id() {
+        let items = [10, 20, 10, 30];
+
+        // A duplicated track loses only the one requested occurrence.
+        assert_eq!(removal_indices(&items, &[10]), vec![0]);
+        // Asking twice removes both copies, highest index first.
+        assert_eq!(removal_indices(&items, &[10, 10]), vec![2, 0]);
+        // Mixed ids come back in descending index order; unknown ids are
+        // skipped.
+        assert_eq!(removal_indices(&items, &[30, 10, 99]), vec![3, 0]);
+        assert!(removal_indices(&items, &[99]).is_empty());
+    }
+}
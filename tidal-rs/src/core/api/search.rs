@@ -27,11 +27,26 @@ impl TidalClient {
     }
 
     pub async fn search(&mut self, query: &str, limit: u32) -> Result<SearchResults> {
+        self.search_with_offset(query, limit, 0).await
+    }
+
+    /// Combined search with paging, for "show more" on a unified results
+    /// view. The combined endpoint does accept `offset` — it applies to each
+    /// type's section independently, the same way `limit` does — so paging
+    /// here advances all sections in lockstep. For paging a single type,
+    /// prefer the per-type searches.
+    pub async fn search_with_offset(
+        &mut self,
+        query: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<SearchResults> {
         let url = self.api_url(
             "search",
             &[
                 ("query", query),
                 ("limit", &limit.to_string()),
+                ("offset", &offset.to_string()),
                 ("types", "ARTISTS,ALBUMS,TRACKS,VIDEOS,PLAYLISTS"),
             ],
         );
@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
 use super::client::TidalClient;
 use super::models::{
+    BATCH_IDS_LIMIT,
+    BatchOutcome,
     Credit,
     ItemsPage,
     Lyrics,
@@ -18,22 +22,62 @@ impl TidalClient {
     }
 
     pub async fn get_tracks(&mut self, track_ids: &[u64]) -> Result<Vec<Track>> {
-        if track_ids.is_empty() {
-            return Ok(vec![]);
-        }
-        let ids = track_ids
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<_>>()
-            .join(",");
-        let url = self.api_url("tracks", &[("ids", &ids)]);
-
         #[derive(Deserialize)]
         struct TracksResponse {
             items: Vec<Track>,
         }
-        let resp: TracksResponse = self.get(&url).await?;
-        Ok(resp.items)
+
+        let mut tracks = Vec::with_capacity(track_ids.len());
+        for chunk in track_ids.chunks(BATCH_IDS_LIMIT) {
+            let ids = chunk
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let url = self.api_url("tracks", &[("ids", &ids)]);
+            let resp: TracksResponse = self.get(&url).await?;
+            tracks.extend(resp.items);
+        }
+        Ok(tracks)
+    }
+
+    /// Fetch a large id set chunk by chunk, fetching chunks concurrently on
+    /// cloned clients (the shared request limiter still applies) and never
+    /// failing the whole call when one chunk does. Items come back in the
+    /// order their ids were requested; unresolved ids are reported in
+    /// [`BatchOutcome::failed`].
+    pub async fn get_tracks_partial(&self, track_ids: &[u64]) -> BatchOutcome<Track> {
+        let fetches = track_ids
+            .chunks(BATCH_IDS_LIMIT)
+            .map(|chunk| {
+                let mut client = self.clone();
+                let chunk = chunk.to_vec();
+                async move {
+                    let result = client.get_tracks(&chunk).await;
+                    (chunk, result)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut items = Vec::with_capacity(track_ids.len());
+        let mut failed = Vec::new();
+        for (chunk, result) in futures::future::join_all(fetches).await {
+            match result {
+                Ok(tracks) => {
+                    let mut by_id: HashMap<u64, Track> =
+                        tracks.into_iter().map(|t| (t.id, t)).collect();
+                    for id in chunk {
+                        match by_id.remove(&id) {
+                            Some(track) => items.push(track),
+                            None => failed.push((id, "not found".to_string())),
+                        }
+                    }
+                }
+                Err(e) => failed.extend(chunk.into_iter().map(|id| (id, e.to_string()))),
+            }
+        }
+
+        BatchOutcome { items, failed }
     }
 
     pub async fn get_track_credits(&mut self, track_id: u64) -> Result<Vec<Credit>> {
@@ -83,6 +127,28 @@ impl TidalClient {
         self.get(&url).await
     }
 
+    /// Fetch a mix's metadata (title, subtitle) by id. There is no
+    /// `mixes/{id}` resource in the v1 API; the pages endpoint is the only
+    /// place a bare mix id resolves to its display metadata, so this pulls
+    /// the title off the mix page.
+    pub async fn get_mix_info(&mut self, mix_id: &str) -> Result<Mix> {
+        #[derive(serde::Deserialize)]
+        struct MixPage {
+            title: Option<String>,
+        }
+
+        let url = self.pages_url(&format!("mix?mixId={}", mix_id), &[]);
+        let page: MixPage = self.get(&url).await?;
+
+        Ok(Mix {
+            id: mix_id.to_string(),
+            title: page.title,
+            sub_title: None,
+            mix_type: None,
+            images: None,
+        })
+    }
+
     pub async fn get_mix_tracks(&mut self, mix_id: &str, limit: u32) -> Result<ItemsPage<MixItem>> {
         let url = self.api_url(
             &format!("mixes/{}/items", mix_id),
@@ -91,6 +157,26 @@ impl TidalClient {
         self.get(&url).await
     }
 
+    /// Look up tracks by ISRC, for matching against external catalogs that
+    /// carry codes rather than Tidal ids. The v1 catalog has no filter
+    /// endpoint, so this searches for the code and keeps exact (case
+    /// insensitive) matches only. All matches are returned — one ISRC often
+    /// maps to several Tidal track ids across regional and reissue
+    /// duplicates, and which one is streamable can differ per market.
+    pub async fn get_track_by_isrc(&mut self, isrc: &str) -> Result<Vec<Track>> {
+        let page = self.search_tracks(isrc, 50, 0).await?;
+        Ok(page
+            .items
+            .into_iter()
+            .filter(|track| {
+                track
+                    .isrc
+                    .as_deref()
+                    .is_some_and(|code| code.eq_ignore_ascii_case(isrc))
+            })
+            .collect())
+    }
+
     pub async fn get_track_full_info(&mut self, track_id: u64) -> Result<TrackFullInfo> {
         let track = self.get_track(track_id).await?;
         let credits = self.get_track_credits(track_id).await.ok();
@@ -1,27 +1,120 @@
+use std::time::{
+    SystemTime,
+    UNIX_EPOCH,
+};
+
 use serde::Deserialize;
 
-use super::client::{
-    API_BASE,
-    TidalClient,
-};
+use super::client::TidalClient;
 use super::models::{
     Folder,
     FolderItem,
     ItemsPage,
+    SessionDevice,
     SessionInfo,
+    SocialPage,
     Subscription,
     UserProfile,
 };
-use crate::core::error::Result;
+use crate::core::error::{
+    Result,
+    TidalError,
+};
 
 impl TidalClient {
-    pub async fn get_session(&mut self) -> Result<SessionInfo> {
-        let session: SessionInfo = self.get(&format!("{}/sessions", API_BASE)).await?;
-        self.country_code = session.country_code.clone();
-        self.user_id = Some(session.user_id);
+    /// Fetches the current session, caching it on first success. Later calls
+    /// return the cached value without a network round-trip; use
+    /// [`TidalClient::session`] to peek at the cache directly.
+    ///
+    /// `&self` precludes the shared retry loop, so its 401 handling is
+    /// mirrored inline: on an expired token a fresh one is minted and the
+    /// fetch retried once with it. The new token can't be stored back on the
+    /// client, but the refresh hook still fires so the application can
+    /// persist it.
+    pub async fn get_session(&self) -> Result<SessionInfo> {
+        if let Some(session) = self.session.get() {
+            return Ok(session.clone());
+        }
+        let url = format!("{}/sessions", self.config.api_base);
+        let session: SessionInfo = match self.get_once(&url).await {
+            Err(TidalError::Api { status: 401, .. }) => {
+                let auth = self.auth.clone().unwrap_or_default();
+                let response = auth.refresh_token(&self.refresh_token).await?;
+                if let Some(callback) = &self.on_token_refresh {
+                    let mut credentials = self.to_credentials();
+                    credentials.access_token = response.access_token.clone();
+                    if !response.refresh_token.is_empty() {
+                        credentials.refresh_token = response.refresh_token.clone();
+                    }
+                    credentials.expires_at = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs()
+                        + response.expires_in;
+                    callback(&credentials);
+                }
+                self.get_once_with_token(&url, &response.access_token)
+                    .await?
+            }
+            other => other?,
+        };
+        let _ = self.session.set(session.clone());
         Ok(session)
     }
 
+    /// List every session the account has open, one entry per logged-in
+    /// device, with the client's name/type and when it was last active.
+    /// Complements [`get_session`](Self::get_session), which only describes
+    /// this client's own session.
+    pub async fn get_active_sessions(&mut self, user_id: u64) -> Result<Vec<SessionDevice>> {
+        let url = self.api_url(&format!("users/{}/sessions", user_id), &[]);
+        let resp: ItemsPage<SessionDevice> = self.get(&url).await?;
+        Ok(resp.items)
+    }
+
+    /// Who follows this user, as public profile summaries. Read-only; uses
+    /// the v2 `profiles/{id}/followers` endpoint the web client calls, which
+    /// answers with the regular session bearer token. Users without a public
+    /// profile 404 here rather than returning an empty page.
+    pub async fn get_user_followers(
+        &mut self,
+        user_id: u64,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<SocialPage> {
+        self.get_social_page("followers", user_id, limit, cursor)
+            .await
+    }
+
+    /// Who this user follows; see
+    /// [`get_user_followers`](Self::get_user_followers) for auth and privacy
+    /// behavior.
+    pub async fn get_user_following(
+        &mut self,
+        user_id: u64,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<SocialPage> {
+        self.get_social_page("following", user_id, limit, cursor)
+            .await
+    }
+
+    async fn get_social_page(
+        &mut self,
+        relation: &str,
+        user_id: u64,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<SocialPage> {
+        let limit = limit.to_string();
+        let mut params = vec![("limit", limit.as_str())];
+        if let Some(cursor) = cursor {
+            params.push(("cursor", cursor));
+        }
+        let url = self.api_v2_url(&format!("profiles/{}/{}", user_id, relation), &params);
+        self.get(&url).await
+    }
+
     pub async fn get_user(&mut self, user_id: u64) -> Result<UserProfile> {
         let url = self.api_url(&format!("users/{}", user_id), &[]);
         self.get(&url).await
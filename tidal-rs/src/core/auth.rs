@@ -1,7 +1,15 @@
+use std::time::Duration;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use serde::{
     Deserialize,
     Serialize,
 };
+use sha2::{
+    Digest,
+    Sha256,
+};
 use uuid::Uuid;
 
 use crate::core::error::{
@@ -11,7 +19,13 @@ use crate::core::error::{
 
 const TV_TOKEN: &str = "7m7Ap0JC9j1cOM3n";
 const TV_SECRET: &str = "vRAdA108tlvkJpTsGZS8rGZ7xTlbJ0qaZ2K9saEzsgY=";
+/// Client id of Tidal's Android app, used by the authorization-code + PKCE
+/// flow. PKCE clients are public, so there is no matching secret.
+const PKCE_CLIENT_ID: &str = "6BDSRdpK9hqEBTgU";
+/// Redirect the Android app registers; the login page only redirects here.
+const PKCE_REDIRECT_URI: &str = "https://tidal.com/android/login/auth";
 const SCOPES: &str = "r_usr w_usr";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
 
 pub const CLIENT_TOKEN: &str = TV_TOKEN;
 
@@ -24,10 +38,29 @@ pub struct Credentials {
     pub country_code: String,
 }
 
+impl Credentials {
+    /// Read credentials previously written by [`save_to`](Self::save_to).
+    pub fn load_from(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Persist as pretty-printed JSON. The parent directory must exist; the
+    /// file holds live tokens, so callers should keep it out of world-readable
+    /// locations.
+    pub fn save_to(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AuthSession {
     pub client_unique_key: String,
     client: reqwest::Client,
+    client_id: String,
+    client_secret: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -62,9 +95,24 @@ pub struct TokenErrorResponse {
 
 impl AuthSession {
     pub fn new() -> Self {
+        Self::with_client(TV_TOKEN, TV_SECRET)
+    }
+
+    /// Use registered client credentials instead of the built-in TV ones —
+    /// for callers with their own Tidal client registration, or a client id
+    /// whose entitlements differ. The device-code and refresh flows send
+    /// these; the PKCE flow keeps its own public client id.
+    pub fn with_client(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to build HTTP client");
+
         Self {
             client_unique_key: Uuid::new_v4().to_string(),
-            client: reqwest::Client::new(),
+            client,
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
         }
     }
 
@@ -80,7 +128,7 @@ impl AuthSession {
         let resp = self
             .client
             .post("https://auth.tidal.com/v1/oauth2/device_authorization")
-            .form(&[("client_id", TV_TOKEN), ("scope", SCOPES)])
+            .form(&[("client_id", self.client_id.as_str()), ("scope", SCOPES)])
             .send()
             .await?;
 
@@ -101,21 +149,29 @@ impl AuthSession {
     }
 
     pub async fn poll_for_token(&self, device_code: &str, interval: u64) -> Result<TokenResponse> {
+        let mut interval = interval;
         loop {
             tokio::time::sleep(tokio::time::Duration::from_secs(interval)).await;
 
-            let resp = self
+            let resp = match self
                 .client
                 .post("https://auth.tidal.com/v1/oauth2/token")
                 .form(&[
-                    ("client_id", TV_TOKEN),
-                    ("client_secret", TV_SECRET),
+                    ("client_id", self.client_id.as_str()),
+                    ("client_secret", self.client_secret.as_str()),
                     ("device_code", device_code),
                     ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
                     ("scope", SCOPES),
                 ])
                 .send()
-                .await?;
+                .await
+            {
+                Ok(resp) => resp,
+                // A stalled poll shouldn't abort the whole flow; treat it as a
+                // missed iteration and poll again.
+                Err(e) if e.is_timeout() => continue,
+                Err(e) => return Err(e.into()),
+            };
 
             let status = resp.status();
             let text = resp.text().await?;
@@ -127,7 +183,10 @@ impl AuthSession {
             if let Ok(err) = serde_json::from_str::<TokenErrorResponse>(&text) {
                 match err.error.as_str() {
                     "authorization_pending" => continue,
-                    "slow_down" => tokio::time::sleep(tokio::time::Duration::from_secs(5)).await,
+                    // RFC 8628 §3.5: on slow_down the polling interval
+                    // increases by 5 seconds for all subsequent requests, not
+                    // just the next one.
+                    "slow_down" => interval += 5,
                     "expired_token" => return Err(TidalError::Auth("code expired".into())),
                     "access_denied" => return Err(TidalError::Auth("access denied".into())),
                     _ => {
@@ -141,13 +200,71 @@ impl AuthSession {
         }
     }
 
+    /// Begin the standard authorization-code + PKCE browser flow, as an
+    /// alternative to the TV device-code flow. Returns the URL to open in a
+    /// browser and the code verifier to hold on to.
+    ///
+    /// The caller owns the redirect handling: after login the browser is
+    /// sent to `https://tidal.com/android/login/auth?code=...`, which won't
+    /// resolve to anything useful — intercept the navigation (embedded
+    /// webview, local proxy, or asking the user to paste the final URL),
+    /// extract the `code` query parameter, and pass it to
+    /// [`complete_pkce_auth`](Self::complete_pkce_auth) together with the
+    /// verifier returned here.
+    pub fn start_pkce_auth(&self) -> (String, String) {
+        // 32 bytes of randomness, base64url-encoded per RFC 7636 §4.1.
+        let mut random = Vec::with_capacity(32);
+        random.extend_from_slice(Uuid::new_v4().as_bytes());
+        random.extend_from_slice(Uuid::new_v4().as_bytes());
+        let verifier = URL_SAFE_NO_PAD.encode(&random);
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+
+        let authorize_url = format!(
+            "https://login.tidal.com/authorize?response_type=code&client_id={}\
+             &redirect_uri={}&scope={}&code_challenge_method=S256&code_challenge={}",
+            PKCE_CLIENT_ID,
+            urlencoding::encode(PKCE_REDIRECT_URI),
+            urlencoding::encode(SCOPES),
+            challenge
+        );
+
+        (authorize_url, verifier)
+    }
+
+    /// Exchange the authorization code captured from the redirect for
+    /// tokens, proving possession of the verifier from
+    /// [`start_pkce_auth`](Self::start_pkce_auth).
+    pub async fn complete_pkce_auth(&self, code: &str, verifier: &str) -> Result<TokenResponse> {
+        let resp = self
+            .client
+            .post("https://auth.tidal.com/v1/oauth2/token")
+            .form(&[
+                ("client_id", PKCE_CLIENT_ID),
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", PKCE_REDIRECT_URI),
+                ("code_verifier", verifier),
+            ])
+            .send()
+            .await?;
+
+        let status = resp.status();
+        let text = resp.text().await?;
+
+        if !status.is_success() {
+            return Err(TidalError::Auth(format!("PKCE exchange failed: {}", text)));
+        }
+
+        Ok(serde_json::from_str(&text)?)
+    }
+
     pub async fn refresh_token(&self, refresh_token: &str) -> Result<TokenResponse> {
         let resp = self
             .client
             .post("https://auth.tidal.com/v1/oauth2/token")
             .form(&[
-                ("client_id", TV_TOKEN),
-                ("client_secret", TV_SECRET),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
                 ("refresh_token", refresh_token),
                 ("grant_type", "refresh_token"),
             ])
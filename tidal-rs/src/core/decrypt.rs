@@ -69,4 +69,35 @@ impl StreamDecryptor {
     pub fn decrypt(&mut self, data: &mut [u8]) {
         self.cipher.apply_keystream(data);
     }
+
+    /// Position the keystream at an absolute byte offset. CTR mode is
+    /// random-access, so a resumed download can seek to the size of the
+    /// partial file and decrypt appended bytes correctly.
+    pub fn seek(&mut self, byte_offset: u64) {
+        use aes::cipher::StreamCipherSeek;
+        self.cipher.seek(byte_offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seek_matches_sequential_keystream() {
+        let key = DecryptionKey {
+            key: [7u8; 16],
+            nonce: [3u8; 8],
+        };
+
+        let mut full = vec![0u8; 64];
+        StreamDecryptor::new(&key).decrypt(&mut full);
+
+        let mut tail = vec![0u8; 24];
+        let mut decryptor = StreamDecryptor::new(&key);
+        decryptor.seek(40);
+        decryptor.decrypt(&mut tail);
+
+        assert_eq!(tail, full[40..]);
+    }
 }
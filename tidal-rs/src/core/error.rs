@@ -3,6 +3,17 @@ use std::fmt;
 #[derive(Debug)]
 pub enum TidalError {
     Api { status: u16, message: String },
+    /// HTTP 412: an `If-None-Match` precondition failed because the resource
+    /// changed under us. Mutation paths catch this to refresh their ETag and
+    /// retry instead of treating it as a generic API failure.
+    Precondition(String),
+    /// HTTP 429 or 503: the request was throttled. `retry_after` carries the
+    /// server's `Retry-After` header when it sent one. `get_with_retry`
+    /// already waits and retries these; callers only see this variant once
+    /// the retry budget is exhausted.
+    RateLimited {
+        retry_after: Option<std::time::Duration>,
+    },
     Auth(String),
     Network(reqwest::Error),
     Json(serde_json::Error),
@@ -17,6 +28,11 @@ impl fmt::Display for TidalError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TidalError::Api { status, message } => write!(f, "API error {}: {}", status, message),
+            TidalError::Precondition(msg) => write!(f, "Precondition failed (412): {}", msg),
+            TidalError::RateLimited { retry_after } => match retry_after {
+                Some(wait) => write!(f, "Rate limited; retry after {:?}", wait),
+                None => write!(f, "Rate limited"),
+            },
             TidalError::Auth(msg) => write!(f, "Authentication failed: {}", msg),
             TidalError::Network(e) => write!(f, "Network error: {}", e),
             TidalError::Json(e) => write!(f, "JSON error: {}", e),
@@ -34,20 +34,34 @@ impl SyncedLyrics {
         let mut lines = Vec::new();
 
         for line in content.lines() {
-            let line = line.trim();
-            if line.is_empty() {
+            let mut rest = line.trim();
+            if rest.is_empty() {
                 continue;
             }
 
-            if let Some(bracket_end) = line.find(']') {
-                let timestamp = &line[1..bracket_end];
-                let text = line[bracket_end + 1..].trim().to_string();
+            // A line may carry several leading timestamps for repeated
+            // sections, e.g. `[00:12.00][01:05.00]Chorus`; collect them all
+            // and emit one entry per occurrence.
+            let mut times = Vec::new();
+            while rest.starts_with('[')
+                && let Some(bracket_end) = rest.find(']')
+            {
+                let Some(time) = parse_lrc_timestamp(&rest[1..bracket_end]) else {
+                    break;
+                };
+                times.push(time);
+                rest = &rest[bracket_end + 1..];
+            }
 
-                if let Some(time) = parse_lrc_timestamp(timestamp) {
-                    if !text.is_empty() {
-                        lines.push(LyricLine { time, text });
-                    }
-                }
+            let text = rest.trim();
+            if text.is_empty() {
+                continue;
+            }
+            for time in times {
+                lines.push(LyricLine {
+                    time,
+                    text: text.to_string(),
+                });
             }
         }
 
@@ -55,7 +69,7 @@ impl SyncedLyrics {
             return None;
         }
 
-        lines.sort_by(|a, b| a.time.cmp(&b.time));
+        lines.sort_by_key(|a| a.time);
         Some(SyncedLyrics { lines })
     }
 
@@ -70,8 +84,8 @@ impl SyncedLyrics {
 
         loop {
             match reader.read_event() {
-                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
-                    if e.name().as_ref() == b"p" {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"p" => {
                         in_p_element = true;
                         current_text.clear();
 
@@ -86,26 +100,22 @@ impl SyncedLyrics {
                             in_p_element = false;
                         }
                     }
-                }
-                Ok(Event::Text(ref e)) => {
-                    if in_p_element {
+                Ok(Event::Text(ref e))
+                    if in_p_element => {
                         let text = String::from_utf8_lossy(e.as_ref());
                         current_text.push_str(&text);
                     }
-                }
-                Ok(Event::End(ref e)) => {
-                    if e.name().as_ref() == b"p" {
+                Ok(Event::End(ref e))
+                    if e.name().as_ref() == b"p" => {
                         in_p_element = false;
 
                         let text = current_text.trim().to_string();
-                        if let Some(time) = current_begin.take() {
-                            if !text.is_empty() {
+                        if let Some(time) = current_begin.take()
+                            && !text.is_empty() {
                                 lines.push(LyricLine { time, text });
                             }
-                        }
                         current_text.clear();
                     }
-                }
                 Ok(Event::Eof) => break,
                 Err(e) => {
                     eprintln!("TTML parse error: {}", e);
@@ -119,10 +129,39 @@ impl SyncedLyrics {
             return None;
         }
 
-        lines.sort_by(|a, b| a.time.cmp(&b.time));
+        lines.sort_by_key(|a| a.time);
         Some(SyncedLyrics { lines })
     }
 
+    /// Render back to LRC, one `[mm:ss.xx]` entry per line. Since
+    /// [`parse`](Self::parse) accepts both LRC and TTML, round-tripping
+    /// through this normalizes whichever format Tidal served into LRC that
+    /// any player reads.
+    pub fn to_lrc(&self) -> String {
+        let mut out = String::new();
+        for line in &self.lines {
+            let total = line.time.as_millis();
+            out.push_str(&format!(
+                "[{:02}:{:02}.{:02}]{}\n",
+                total / 60_000,
+                (total % 60_000) / 1000,
+                (total % 1000) / 10,
+                line.text
+            ));
+        }
+        out
+    }
+
+    /// The lyric text with timestamps dropped, one line per entry — for
+    /// embedding in tags or display where synchronization is unwanted.
+    pub fn to_plain_text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     pub fn line_at(&self, position: Duration) -> Option<&LyricLine> {
         if self.lines.is_empty() {
             return None;
@@ -186,7 +225,7 @@ impl SyncedLyrics {
 }
 
 fn parse_lrc_timestamp(s: &str) -> Option<Duration> {
-    let parts: Vec<&str> = s.split(|c| c == ':' || c == '.').collect();
+    let parts: Vec<&str> = s.split([':', '.']).collect();
 
     match parts.len() {
         2 => {
@@ -271,3 +310,42 @@ impl LyricsDisplay {
         &self.lyrics
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lrc_round_trip_is_stable_and_plain_text_drops_timestamps() {
+        let canonical = "[00:05.00]Verse one\n[00:12.50]Chorus\n[01:05.03]Verse two\n";
+        let lyrics = SyncedLyrics::parse(canonical).unwrap();
+
+        let rendered = lyrics.to_lrc();
+        assert_eq!(rendered, canonical);
+        // A second pass through parse/to_lrc must not drift.
+        assert_eq!(SyncedLyrics::parse(&rendered).unwrap().to_lrc(), canonical);
+
+        assert_eq!(lyrics.to_plain_text(), "Verse one\nChorus\nVerse two");
+    }
+
+    #[test]
+    fn multi_timestamp_lrc_lines_repeat_the_text() {
+        let lrc = "[00:05.00]Verse one\n[00:12.00][01:05.50]Chorus\n[00:30.00]Verse two\n";
+        let lyrics = SyncedLyrics::parse(lrc).unwrap();
+
+        let rendered: Vec<(u128, &str)> = lyrics
+            .lines
+            .iter()
+            .map(|l| (l.time.as_millis(), l.text.as_str()))
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                (5_000, "Verse one"),
+                (12_000, "Chorus"),
+                (30_000, "Verse two"),
+                (65_500, "Chorus"),
+            ]
+        );
+    }
+}
@@ -1,11 +1,22 @@
+use std::path::{
+    Path,
+    PathBuf,
+};
 use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
 
 use bytes::Bytes;
 use futures::Stream;
 
 use crate::core::api::{
     PlaybackInfo,
+    PlaybackMode,
     TidalClient,
+    Track,
 };
 use crate::core::decrypt::{
     StreamDecryptor,
@@ -16,7 +27,7 @@ use crate::core::error::{
     TidalError,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AudioQuality {
     Low,
     High,
@@ -37,6 +48,96 @@ impl AudioQuality {
     }
 }
 
+impl std::fmt::Display for AudioQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses the API names (`LOW`, `HIGH`, `LOSSLESS`, `HI_RES`,
+/// `HI_RES_LOSSLESS`) case-insensitively, plus the aliases Tidal's own
+/// marketing uses: `hifi` for the CD-quality tier and `max` for the top one.
+impl std::str::FromStr for AudioQuality {
+    type Err = TidalError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "low" => Ok(AudioQuality::Low),
+            "high" => Ok(AudioQuality::High),
+            "lossless" | "hifi" => Ok(AudioQuality::Lossless),
+            "hi_res" | "hires" => Ok(AudioQuality::HiRes),
+            "hi_res_lossless" | "hireslossless" | "max" => Ok(AudioQuality::HiResLossless),
+            other => Err(TidalError::Decode(format!(
+                "Unknown audio quality \"{}\"; expected LOW, HIGH, LOSSLESS, \
+                 HI_RES, HI_RES_LOSSLESS (or the aliases hifi/max)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Quality tiers for video playback. Unlike [`AudioQuality`] these are
+/// resolution classes, and the backend picks the actual variant ladder; the
+/// tier caps which variants show up in the returned HLS master playlist.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VideoQuality {
+    AudioOnly,
+    Low,
+    Medium,
+    High,
+}
+
+impl VideoQuality {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VideoQuality::AudioOnly => "AUDIO_ONLY",
+            VideoQuality::Low => "LOW",
+            VideoQuality::Medium => "MEDIUM",
+            VideoQuality::High => "HIGH",
+        }
+    }
+}
+
+impl std::fmt::Display for VideoQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One entry of an HLS master playlist: a media-playlist URL plus the
+/// `BANDWIDTH`/`RESOLUTION` attributes advertised for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HlsVariant {
+    pub url: String,
+    pub bandwidth: Option<u64>,
+    /// As written in the playlist, e.g. "1920x1080".
+    pub resolution: Option<String>,
+}
+
+/// Video analogue of [`StreamInfo`]: the variant streams parsed from the
+/// HLS master playlist. Each variant URL is a media playlist, not raw bytes
+/// — hand it to an HLS-capable player (or fetch and walk the segment list
+/// yourself).
+#[derive(Debug, Clone)]
+pub struct VideoStreamInfo {
+    pub video_id: u64,
+    pub variants: Vec<HlsVariant>,
+    /// The quality tier the caller asked for, e.g. "HIGH".
+    pub requested_quality: String,
+    /// The tier the backend granted, echoed by the playback-info endpoint.
+    pub actual_quality: String,
+}
+
+impl VideoStreamInfo {
+    /// The highest-bandwidth variant — usually what a downloader wants.
+    /// Variants without a `BANDWIDTH` attribute sort last.
+    pub fn best_variant(&self) -> Option<&HlsVariant> {
+        self.variants
+            .iter()
+            .max_by_key(|variant| variant.bandwidth.unwrap_or(0))
+    }
+}
+
 #[derive(Debug)]
 pub struct StreamInfo {
     pub track_id: u64,
@@ -46,21 +147,226 @@ pub struct StreamInfo {
     pub sample_rate: Option<u32>,
     pub bit_depth: Option<u32>,
     pub encryption: Option<StreamDecryptor>,
+    /// The quality the caller asked for, e.g. "HI_RES_LOSSLESS".
+    pub requested_quality: String,
+    /// The quality Tidal actually granted (echoed by the playback-info
+    /// endpoint); differs from `requested_quality` when the subscription or
+    /// catalog caps the stream.
+    pub actual_quality: String,
+}
+
+/// The raw ingredients of a stream for players that fetch and decrypt the
+/// bytes themselves (an external decoder, or a native player behind FFI):
+/// segment URLs in playback order plus, for `OLD_AES`-encrypted BTS streams,
+/// the AES-128-CTR key material.
+///
+/// Security note: `key`/`nonce` decrypt the protected stream directly.
+/// Treat a `StreamManifest` like the audio it unlocks — don't log it, don't
+/// persist it, and don't hand it across a trust boundary that shouldn't be
+/// able to read the track. Callers that only need playback inside this
+/// process should prefer [`TidalClient::get_stream`], which never exposes
+/// the key.
+#[derive(Debug, Clone)]
+pub struct StreamManifest {
+    pub track_id: u64,
+    /// Segment URLs in playback order; a single element for BTS streams.
+    pub urls: Vec<String>,
+    pub mime_type: String,
+    pub codecs: String,
+    pub sample_rate: Option<u32>,
+    pub bit_depth: Option<u32>,
+    pub requested_quality: String,
+    pub actual_quality: String,
+    /// AES-128 key for `OLD_AES` BTS streams; `None` means the bytes are
+    /// served in the clear.
+    pub key: Option<[u8; 16]>,
+    /// CTR nonce (upper 8 IV bytes, counter starts at zero) paired with
+    /// `key`.
+    pub nonce: Option<[u8; 8]>,
+}
+
+impl StreamManifest {
+    /// Whether the segments need decrypting before they are playable.
+    pub fn is_encrypted(&self) -> bool {
+        self.key.is_some()
+    }
 }
 
 pub type BoxedByteStream = Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>;
 
+/// Events yielded by [`TidalClient::album_stream`]: a boundary marker before
+/// each track, followed by that track's decrypted audio.
+#[derive(Debug)]
+pub enum TrackAudioEvent {
+    TrackStarted(Box<Track>),
+    Chunk(Bytes),
+}
+
+pub type BoxedAudioEventStream = Pin<Box<dyn Stream<Item = Result<TrackAudioEvent>> + Send>>;
+
+/// Cooperative cancellation for [`TidalClient::download_album`]. Cloning
+/// shares the flag, so the caller keeps one copy and hands the other to the
+/// download; in-flight tracks stop at the next segment boundary.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Knobs for [`TidalClient::download_album`].
+#[derive(Debug, Clone)]
+pub struct AlbumDownloadOptions {
+    /// How many tracks to download at once. The client's shared request
+    /// limiter still applies to the API calls underneath.
+    pub concurrency: usize,
+    pub cancel: CancelToken,
+}
+
+impl Default for AlbumDownloadOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 1,
+            cancel: CancelToken::new(),
+        }
+    }
+}
+
+/// Events yielded by [`TidalClient::download_album`]. Per-track failures are
+/// events rather than stream errors so one bad track doesn't end the album.
+#[derive(Debug)]
+pub enum DownloadEvent {
+    TrackStarted(Box<Track>),
+    TrackProgress {
+        track_id: u64,
+        downloaded_bytes: u64,
+    },
+    TrackCompleted {
+        track_id: u64,
+        path: PathBuf,
+    },
+    TrackFailed {
+        track_id: u64,
+        error: TidalError,
+    },
+    /// Listing the album's tracks failed; nothing was downloaded.
+    AlbumFailed(TidalError),
+    /// The token was cancelled; emitted once, after in-flight tracks stop.
+    Cancelled,
+}
+
+pub type BoxedDownloadEventStream = Pin<Box<dyn Stream<Item = DownloadEvent> + Send>>;
+
+/// Progress observer for consumers that can't pass a Rust closure (FFI
+/// bindings, GUI glue). [`TidalClient::download_track_with_observer`] calls
+/// it with the same arguments the closure-based
+/// [`TidalClient::download_track_with_progress`] receives.
+pub trait DownloadProgress: Send + Sync {
+    /// Called after every received chunk with bytes downloaded so far and
+    /// the total when known (`Content-Length`, or summed segment sizes).
+    fn on_progress(&self, downloaded_bytes: u64, total_bytes: Option<u64>);
+}
+
 impl TidalClient {
     pub async fn get_stream_info(
         &mut self,
         track_id: u64,
         quality: AudioQuality,
     ) -> Result<StreamInfo> {
-        let playback_info = self.get_playback_info(track_id, quality.as_str()).await?;
-        self.parse_stream_info(playback_info)
+        let playback_info = self
+            .get_playback_info(track_id, quality.as_str(), PlaybackMode::Stream, false)
+            .await?;
+        self.parse_stream_info(playback_info, quality.as_str())
+    }
+
+    /// Fetch the playback manifest for a track without downloading anything:
+    /// segment URLs plus, for encrypted BTS streams, the decrypted key and
+    /// nonce, so an external player can fetch and decrypt the bytes itself.
+    /// See the security note on [`StreamManifest`] before passing the result
+    /// anywhere.
+    pub async fn get_stream_manifest(
+        &mut self,
+        track_id: u64,
+        quality: AudioQuality,
+    ) -> Result<StreamManifest> {
+        let playback_info = self
+            .get_playback_info(track_id, quality.as_str(), PlaybackMode::Stream, false)
+            .await?;
+
+        match playback_info.manifest_mime_type.as_str() {
+            "application/vnd.tidal.bts" => {
+                let manifest = self.decode_bts_manifest(&playback_info)?;
+                let (key, nonce) = match manifest.encryption_type.as_str() {
+                    "OLD_AES" => {
+                        let key_id = manifest
+                            .key_id
+                            .as_ref()
+                            .ok_or_else(|| TidalError::Encryption("Missing keyId".into()))?;
+                        let dec_key = decrypt_key_id(key_id)?;
+                        (Some(dec_key.key), Some(dec_key.nonce))
+                    }
+                    "NONE" => (None, None),
+                    other => {
+                        return Err(TidalError::Encryption(format!(
+                            "Unknown encryption: {}",
+                            other
+                        )));
+                    }
+                };
+
+                Ok(StreamManifest {
+                    track_id: playback_info.track_id,
+                    urls: manifest.urls,
+                    mime_type: manifest.mime_type,
+                    codecs: manifest.codecs,
+                    sample_rate: playback_info.sample_rate,
+                    bit_depth: playback_info.bit_depth,
+                    requested_quality: quality.as_str().to_string(),
+                    actual_quality: playback_info.audio_quality,
+                    key,
+                    nonce,
+                })
+            }
+            "application/dash+xml" => {
+                let manifest = self.decode_dash_manifest(&playback_info)?;
+
+                Ok(StreamManifest {
+                    track_id: playback_info.track_id,
+                    urls: manifest.urls,
+                    mime_type: manifest.mime_type,
+                    codecs: manifest.codecs,
+                    sample_rate: playback_info.sample_rate,
+                    bit_depth: playback_info.bit_depth,
+                    requested_quality: quality.as_str().to_string(),
+                    actual_quality: playback_info.audio_quality,
+                    key: None,
+                    nonce: None,
+                })
+            }
+            other => Err(TidalError::Manifest(format!(
+                "Track {} returned an unsupported manifest type \"{}\" for \
+                 external playback; only vnd.tidal.bts and dash+xml carry \
+                 fetchable segment URLs",
+                playback_info.track_id, other
+            ))),
+        }
     }
 
-    fn parse_stream_info(&self, playback_info: PlaybackInfo) -> Result<StreamInfo> {
+    fn parse_stream_info(
+        &self,
+        playback_info: PlaybackInfo,
+        requested_quality: &str,
+    ) -> Result<StreamInfo> {
         match playback_info.manifest_mime_type.as_str() {
             "application/vnd.tidal.bts" => {
                 let manifest = self.decode_bts_manifest(&playback_info)?;
@@ -90,6 +396,8 @@ impl TidalClient {
                     sample_rate: playback_info.sample_rate,
                     bit_depth: playback_info.bit_depth,
                     encryption,
+                    requested_quality: requested_quality.to_string(),
+                    actual_quality: playback_info.audio_quality,
                 })
             }
             "application/dash+xml" => {
@@ -103,11 +411,24 @@ impl TidalClient {
                     sample_rate: playback_info.sample_rate,
                     bit_depth: playback_info.bit_depth,
                     encryption: None,
+                    requested_quality: requested_quality.to_string(),
+                    actual_quality: playback_info.audio_quality,
                 })
             }
+            // "Enhanced" manifests (vnd.tidal.emu) wrap DRM-protected content
+            // (typically videos and some Dolby Atmos streams). The payload is
+            // a license challenge, not a segment list, so there's nothing to
+            // build a StreamInfo from without a DRM stack.
+            "application/vnd.tidal.emu" => Err(TidalError::Manifest(format!(
+                "Track {} returned a DRM-protected EMU manifest; this content \
+                 can't be streamed without a DRM client. Try a different \
+                 quality, which may come back as an unprotected manifest",
+                playback_info.track_id
+            ))),
             other => Err(TidalError::Manifest(format!(
-                "Unknown manifest type: {}",
-                other
+                "Track {} returned an unknown manifest type \"{}\"; only \
+                 vnd.tidal.bts and dash+xml manifests are supported",
+                playback_info.track_id, other
             ))),
         }
     }
@@ -130,6 +451,323 @@ impl TidalClient {
         Ok(data)
     }
 
+    /// Stream a track's bytes as they arrive instead of buffering the whole
+    /// file: segments are fetched lazily in manifest order and concatenated,
+    /// and CTR decryption is applied chunk by chunk (the keystream advances
+    /// with the bytes, so per-chunk decryption is exact). This is the
+    /// building block for piping audio straight into a player or an HTTP
+    /// response. A network error ends the stream after yielding it.
+    pub fn get_stream(&self, stream_info: StreamInfo) -> BoxedByteStream {
+        struct State {
+            client: reqwest::Client,
+            urls: std::vec::IntoIter<String>,
+            current: Option<reqwest::Response>,
+            decryptor: Option<StreamDecryptor>,
+            failed: bool,
+        }
+
+        let state = State {
+            client: self.client.clone(),
+            urls: stream_info.urls.into_iter(),
+            current: None,
+            decryptor: stream_info.encryption,
+            failed: false,
+        };
+
+        Box::pin(futures::stream::unfold(state, |mut state| async move {
+            if state.failed {
+                return None;
+            }
+            loop {
+                let resp = match state.current.as_mut() {
+                    Some(resp) => resp,
+                    None => {
+                        let url = state.urls.next()?;
+                        match state.client.get(&url).send().await {
+                            Ok(resp) => state.current.insert(resp),
+                            Err(e) => {
+                                state.failed = true;
+                                return Some((Err(e.into()), state));
+                            }
+                        }
+                    }
+                };
+
+                match resp.chunk().await {
+                    Ok(Some(chunk)) => {
+                        let mut bytes = chunk.to_vec();
+                        if let Some(ref mut decryptor) = state.decryptor {
+                            decryptor.decrypt(&mut bytes);
+                        }
+                        return Some((Ok(Bytes::from(bytes)), state));
+                    }
+                    Ok(None) => {
+                        state.current = None;
+                        continue;
+                    }
+                    Err(e) => {
+                        state.failed = true;
+                        return Some((Err(e.into()), state));
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Like [`get_stream_bytes`](Self::get_stream_bytes), but fetches up to
+    /// `concurrency` segments in parallel — a large win for DASH tracks with
+    /// hundreds of small segments. `buffered` yields results in request
+    /// order, and decryption runs only after each segment is fully
+    /// reassembled, so the stateful CTR decryptor still sees the bytes in
+    /// sequence. Around 8 is a good concurrency for segment-sized requests.
+    pub async fn get_stream_bytes_concurrent(
+        &mut self,
+        stream_info: &mut StreamInfo,
+        concurrency: usize,
+    ) -> Result<Vec<u8>> {
+        use futures::StreamExt;
+
+        let client = self.client.clone();
+        let segments: Vec<Vec<u8>> = futures::stream::iter(stream_info.urls.clone())
+            .map(|url| {
+                let client = client.clone();
+                async move {
+                    let resp = client.get(&url).send().await?;
+                    Ok::<_, TidalError>(resp.bytes().await?.to_vec())
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_>>()?;
+
+        let mut data = Vec::new();
+        for mut segment in segments {
+            if let Some(ref mut decryptor) = stream_info.encryption {
+                decryptor.decrypt(&mut segment);
+            }
+            data.extend(segment);
+        }
+
+        Ok(data)
+    }
+
+    /// Stream a whole album as one continuous sequence of audio chunks with
+    /// [`TrackAudioEvent::TrackStarted`] boundaries between tracks, so a
+    /// gapless player can crossfade or butt-join without managing per-track
+    /// fetches itself. Tracks arrive in the album's own order; a send error
+    /// simply means the consumer dropped the stream.
+    pub fn album_stream(&self, album_id: u64, quality: AudioQuality) -> BoxedAudioEventStream {
+        let mut client = self.clone();
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<TrackAudioEvent>>(8);
+
+        tokio::spawn(async move {
+            let run = async {
+                let tracks = client.get_all_album_tracks(album_id).await?;
+
+                for track in tracks {
+                    let track_id = track.id;
+                    if tx
+                        .send(Ok(TrackAudioEvent::TrackStarted(Box::new(track))))
+                        .await
+                        .is_err()
+                    {
+                        return Ok(());
+                    }
+
+                    let mut stream_info =
+                        client.get_stream_info(track_id, quality.clone()).await?;
+                    let data = client.get_stream_bytes(&mut stream_info).await?;
+                    if tx
+                        .send(Ok(TrackAudioEvent::Chunk(Bytes::from(data))))
+                        .await
+                        .is_err()
+                    {
+                        return Ok(());
+                    }
+                }
+
+                Ok(())
+            };
+
+            if let Err(e) = run.await {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+
+        Box::pin(futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        }))
+    }
+
+    /// Download a whole album to `dir`, reporting per-track progress,
+    /// completion and failure as a stream of [`DownloadEvent`]s. Unlike the
+    /// CLI's inline loop this is reusable from GUI/FFI consumers: it runs up
+    /// to `opts.concurrency` tracks at once, stops at the next segment
+    /// boundary when `opts.cancel` fires, and keeps going past individual
+    /// track failures. Files are named `NN - Title.ext` with path separators
+    /// replaced.
+    pub fn download_album(
+        &self,
+        album_id: u64,
+        quality: AudioQuality,
+        dir: &Path,
+        opts: AlbumDownloadOptions,
+    ) -> BoxedDownloadEventStream {
+        let client = self.clone();
+        let dir = dir.to_path_buf();
+        let (tx, rx) = tokio::sync::mpsc::channel::<DownloadEvent>(8);
+
+        tokio::spawn(async move {
+            let mut list_client = client.clone();
+            let tracks = match list_client.get_all_album_tracks(album_id).await {
+                Ok(tracks) => tracks,
+                Err(e) => {
+                    let _ = tx.send(DownloadEvent::AlbumFailed(e)).await;
+                    return;
+                }
+            };
+
+            let limiter = Arc::new(tokio::sync::Semaphore::new(opts.concurrency.max(1)));
+            let mut handles = Vec::new();
+            for track in tracks {
+                if opts.cancel.is_cancelled() {
+                    break;
+                }
+                let Ok(permit) = limiter.clone().acquire_owned().await else {
+                    break;
+                };
+
+                let mut client = client.clone();
+                let tx = tx.clone();
+                let cancel = opts.cancel.clone();
+                let dir = dir.clone();
+                let quality = quality.clone();
+                handles.push(tokio::spawn(async move {
+                    use tokio::io::AsyncWriteExt;
+
+                    let _permit = permit;
+                    if cancel.is_cancelled() {
+                        return;
+                    }
+
+                    let track_id = track.id;
+                    let number = track.track_number.unwrap_or(0);
+                    let title = track.title.replace(['/', '\\'], "_");
+                    if tx
+                        .send(DownloadEvent::TrackStarted(Box::new(track)))
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+
+                    let result: Result<Option<PathBuf>> = async {
+                        let mut info = client.get_stream_info(track_id, quality).await?;
+                        let path = dir.join(format!(
+                            "{:02} - {}.{}",
+                            number,
+                            title,
+                            info.file_extension()
+                        ));
+                        let http = reqwest::Client::new();
+                        let mut file = tokio::fs::File::create(&path).await?;
+                        let mut downloaded = 0u64;
+
+                        let urls = std::mem::take(&mut info.urls);
+                        for url in &urls {
+                            if cancel.is_cancelled() {
+                                return Ok(None);
+                            }
+                            let mut bytes = http.get(url).send().await?.bytes().await?.to_vec();
+                            if let Some(ref mut decryptor) = info.encryption {
+                                decryptor.decrypt(&mut bytes);
+                            }
+                            file.write_all(&bytes).await?;
+                            downloaded += bytes.len() as u64;
+                            let _ = tx
+                                .send(DownloadEvent::TrackProgress {
+                                    track_id,
+                                    downloaded_bytes: downloaded,
+                                })
+                                .await;
+                        }
+
+                        file.flush().await?;
+                        Ok(Some(path))
+                    }
+                    .await;
+
+                    let event = match result {
+                        Ok(Some(path)) => DownloadEvent::TrackCompleted { track_id, path },
+                        Ok(None) => return,
+                        Err(error) => DownloadEvent::TrackFailed { track_id, error },
+                    };
+                    let _ = tx.send(event).await;
+                }));
+            }
+
+            for handle in handles {
+                let _ = handle.await;
+            }
+            if opts.cancel.is_cancelled() {
+                let _ = tx.send(DownloadEvent::Cancelled).await;
+            }
+        });
+
+        Box::pin(futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        }))
+    }
+
+    /// Total size of a stream in bytes, established before downloading
+    /// anything. HEAD requests run concurrently across the segment URLs
+    /// (each URL, initialization segment included, appears exactly once in
+    /// `urls`, so a plain sum cannot double-count). Servers that strip
+    /// `Content-Length` from HEAD responses get a second chance via a
+    /// one-byte `Range` probe and its `Content-Range` total. Returns `None`
+    /// when any segment's size still can't be established — a partial sum
+    /// would only mislead a progress bar; use
+    /// [`estimate_download_size`](Self::estimate_download_size) when a lower
+    /// bound is good enough.
+    pub async fn estimate_size(&self, stream_info: &StreamInfo) -> Result<Option<u64>> {
+        use futures::StreamExt;
+
+        const HEAD_CONCURRENCY: usize = 8;
+
+        let client = self.client.clone();
+        let sizes: Vec<Option<u64>> = futures::stream::iter(stream_info.urls.clone())
+            .map(|url| {
+                let client = client.clone();
+                async move { segment_size(&client, &url).await }
+            })
+            .buffered(HEAD_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_>>()?;
+
+        Ok(sizes.into_iter().sum())
+    }
+
+    /// Estimate the download size of a stream by issuing a HEAD request per
+    /// segment URL and summing `Content-Length`, without fetching any bodies.
+    /// Single-file BTS streams cost one request; DASH streams one per
+    /// segment. Servers that omit the header contribute zero, so the result
+    /// is a lower bound.
+    pub async fn estimate_download_size(&self, stream_info: &StreamInfo) -> Result<u64> {
+        let client = reqwest::Client::new();
+        let mut total = 0u64;
+
+        for url in &stream_info.urls {
+            let resp = client.head(url).send().await?;
+            total += resp.content_length().unwrap_or(0);
+        }
+
+        Ok(total)
+    }
+
     pub async fn download_track(
         &mut self,
         track_id: u64,
@@ -147,6 +785,218 @@ impl TidalClient {
 
         Ok(())
     }
+
+    /// Like [`download_track`](Self::download_track), but reports progress
+    /// after every received chunk: bytes downloaded so far, plus the total
+    /// when one HEAD pass could establish it. CTR decryption runs chunk by
+    /// chunk (the keystream advances with the bytes), so the file is written
+    /// incrementally rather than buffered whole.
+    pub async fn download_track_with_progress(
+        &mut self,
+        track_id: u64,
+        quality: AudioQuality,
+        output_path: &str,
+        mut progress: impl FnMut(u64, Option<u64>),
+    ) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut stream_info = self.get_stream_info(track_id, quality).await?;
+        let total = self
+            .estimate_download_size(&stream_info)
+            .await
+            .ok()
+            .filter(|&t| t > 0);
+
+        let mut file = tokio::fs::File::create(output_path).await?;
+        let mut downloaded = 0u64;
+        let urls = std::mem::take(&mut stream_info.urls);
+
+        for url in &urls {
+            let mut resp = self.client.get(url).send().await?;
+            while let Some(chunk) = resp.chunk().await? {
+                let mut bytes = chunk.to_vec();
+                if let Some(ref mut decryptor) = stream_info.encryption {
+                    decryptor.decrypt(&mut bytes);
+                }
+                file.write_all(&bytes).await?;
+                downloaded += bytes.len() as u64;
+                progress(downloaded, total);
+            }
+        }
+
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// Trait-object variant of
+    /// [`download_track_with_progress`](Self::download_track_with_progress)
+    /// for callers that can't construct a closure.
+    pub async fn download_track_with_observer(
+        &mut self,
+        track_id: u64,
+        quality: AudioQuality,
+        output_path: &str,
+        progress: Arc<dyn DownloadProgress>,
+    ) -> Result<()> {
+        self.download_track_with_progress(track_id, quality, output_path, |downloaded, total| {
+            progress.on_progress(downloaded, total)
+        })
+        .await
+    }
+
+    /// Like [`download_track`](Self::download_track), but picks up where a
+    /// previous interrupted run left off instead of starting from zero.
+    ///
+    /// Single-URL (BTS) streams resume with a `Range: bytes=<n>-` request and
+    /// append; multi-URL DASH streams skip whole segments already on disk,
+    /// sized via HEAD requests. Both supported encryption types resume
+    /// cleanly: `NONE` trivially, and `OLD_AES` because its AES-CTR keystream
+    /// is seekable ([`StreamDecryptor::seek`]). If the server ignores the
+    /// range request, or the partial file doesn't line up with whole
+    /// segments, the download restarts from scratch.
+    pub async fn download_track_resumable(
+        &mut self,
+        track_id: u64,
+        quality: AudioQuality,
+        output_path: &str,
+    ) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut stream_info = self.get_stream_info(track_id, quality).await?;
+        let existing = tokio::fs::metadata(output_path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        let http = self.client.clone();
+
+        if let [url] = stream_info.urls.as_slice() {
+            let url = url.clone();
+            let mut offset = existing;
+            let mut resp = None;
+            if offset > 0 {
+                let ranged = http
+                    .get(&url)
+                    .header(reqwest::header::RANGE, format!("bytes={}-", offset))
+                    .send()
+                    .await?;
+                if ranged.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+                    resp = Some(ranged);
+                } else {
+                    // Server ignored the range; restart from scratch.
+                    offset = 0;
+                }
+            }
+            let resp = match resp {
+                Some(resp) => resp,
+                None => http.get(&url).send().await?,
+            };
+
+            if let Some(ref mut decryptor) = stream_info.encryption {
+                decryptor.seek(offset);
+            }
+
+            let mut file = if offset > 0 {
+                tokio::fs::OpenOptions::new()
+                    .append(true)
+                    .open(output_path)
+                    .await?
+            } else {
+                tokio::fs::File::create(output_path).await?
+            };
+
+            let mut bytes = resp.bytes().await?.to_vec();
+            if let Some(ref mut decryptor) = stream_info.encryption {
+                decryptor.decrypt(&mut bytes);
+            }
+            file.write_all(&bytes).await?;
+            file.flush().await?;
+            return Ok(());
+        }
+
+        // DASH: figure out how many whole segments the partial file covers.
+        // A segment with no Content-Length, or a size that doesn't land
+        // exactly on a segment boundary, means the file can't be trusted.
+        let mut resume = existing > 0;
+        let mut skip_bytes = 0u64;
+        let mut skip_segments = 0usize;
+        if resume {
+            for url in &stream_info.urls {
+                let Some(len) = http.head(url).send().await?.content_length() else {
+                    resume = false;
+                    break;
+                };
+                if skip_bytes + len > existing {
+                    break;
+                }
+                skip_bytes += len;
+                skip_segments += 1;
+                if skip_bytes == existing {
+                    break;
+                }
+            }
+            if skip_bytes != existing {
+                resume = false;
+            }
+        }
+        if !resume {
+            skip_bytes = 0;
+            skip_segments = 0;
+        }
+
+        if let Some(ref mut decryptor) = stream_info.encryption {
+            decryptor.seek(skip_bytes);
+        }
+
+        let mut file = if resume {
+            tokio::fs::OpenOptions::new()
+                .append(true)
+                .open(output_path)
+                .await?
+        } else {
+            tokio::fs::File::create(output_path).await?
+        };
+
+        let urls = std::mem::take(&mut stream_info.urls);
+        for url in &urls[skip_segments..] {
+            let mut bytes = http.get(url).send().await?.bytes().await?.to_vec();
+            if let Some(ref mut decryptor) = stream_info.encryption {
+                decryptor.decrypt(&mut bytes);
+            }
+            file.write_all(&bytes).await?;
+        }
+        file.flush().await?;
+
+        Ok(())
+    }
+}
+
+/// Reduce decoded PCM to a normalized peak array for waveform/scrubbing UIs:
+/// one peak (max absolute amplitude) per bucket, scaled so the loudest bucket
+/// is 1.0.
+///
+/// Tidal exposes no server-side peaks endpoint, so the samples have to come
+/// from client-side decoding — typically of the 30-second preview clip.
+/// Decoding is left to the caller; that keeps the crate free of an audio
+/// decoder dependency while still giving UIs the bounded part (downsampling)
+/// for free.
+pub fn compute_peaks(samples: &[f32], buckets: usize) -> Vec<f32> {
+    if buckets == 0 || samples.is_empty() {
+        return Vec::new();
+    }
+
+    let bucket_len = samples.len().div_ceil(buckets);
+    let mut peaks: Vec<f32> = samples
+        .chunks(bucket_len)
+        .map(|chunk| chunk.iter().fold(0f32, |acc, s| acc.max(s.abs())))
+        .collect();
+
+    let max = peaks.iter().copied().fold(0f32, f32::max);
+    if max > 0.0 {
+        for peak in &mut peaks {
+            *peak /= max;
+        }
+    }
+    peaks
 }
 
 impl StreamInfo {
@@ -159,7 +1009,292 @@ impl StreamInfo {
         }
     }
 
+    /// Like [`file_extension`](Self::file_extension), but only answers when
+    /// `codecs`/`mime_type` actually identify the container. This lets callers
+    /// compute the output path (and run skip-existing checks) before
+    /// downloading, falling back to sniffing the bytes only when ambiguous.
+    pub fn expected_extension(&self) -> Option<&'static str> {
+        match self.codecs.as_str() {
+            "flac" => Some("flac"),
+            "mp4a.40.2" | "mp4a.40.5" => Some("m4a"),
+            _ if self.mime_type.contains("flac") => Some("flac"),
+            _ if self.mime_type.contains("mp4") => Some("m4a"),
+            _ => None,
+        }
+    }
+
+    /// Whether Tidal granted a lower quality than requested (subscription
+    /// cap, catalog availability, etc.).
+    pub fn was_downgraded(&self) -> bool {
+        !self.actual_quality.is_empty() && self.requested_quality != self.actual_quality
+    }
+
     pub fn is_lossless(&self) -> bool {
         self.codecs == "flac" || self.mime_type.contains("flac")
     }
+
+    /// Whether the stream is (likely) MQA-encoded, judged from the playback
+    /// metadata alone. Explicit `mqa` codec strings are definitive; beyond
+    /// that, the legacy `HI_RES` tier is MQA FLAC — a 44.1/48 kHz container
+    /// carrying a folded higher-rate master — so a hi-res grant at a CD-class
+    /// sample rate is flagged too. For byte-level confirmation of a
+    /// downloaded file, see [`detect_mqa_from_header`].
+    pub fn is_mqa(&self) -> bool {
+        if self.codecs.to_ascii_lowercase().contains("mqa")
+            || self.mime_type.to_ascii_lowercase().contains("mqa")
+        {
+            return true;
+        }
+        self.actual_quality == "HI_RES"
+            && self.is_lossless()
+            && self.sample_rate.is_none_or(|rate| rate <= 48_000)
+    }
+
+    /// Human-readable codec name for display, e.g. "AAC" for the low-bitrate
+    /// `mp4a` streams returned at `AudioQuality::Low`/`High`.
+    pub fn codec_display(&self) -> &str {
+        match self.codecs.as_str() {
+            "flac" => "FLAC",
+            "mp4a.40.2" => "AAC",
+            "mp4a.40.5" => "HE-AAC",
+            other => other,
+        }
+    }
+}
+
+/// What the STREAMINFO block of a verified FLAC stream declares. `md5` is
+/// the signature of the decoded audio; encoders that skip it leave it all
+/// zeroes, so a non-zero value is available for decode verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlacInfo {
+    pub sample_rate: u32,
+    pub bit_depth: u32,
+    pub total_samples: u64,
+    pub md5: [u8; 16],
+}
+
+/// Validate a reassembled FLAC download and return what its STREAMINFO
+/// declares.
+///
+/// Checks the `fLaC` marker and parses the mandatory first metadata block;
+/// a truncated or mis-decrypted stream fails here instead of being written
+/// to disk and silently treated as FLAC. The declared sample rate must be
+/// non-zero (zero is invalid per the spec and a reliable sign of garbage
+/// bytes that happen to start with the marker).
+pub fn verify_flac(data: &[u8]) -> Result<FlacInfo> {
+    let bad = |msg: &str| TidalError::Decode(format!("Invalid FLAC stream: {}", msg));
+
+    if !data.starts_with(b"fLaC") {
+        return Err(bad("missing fLaC marker"));
+    }
+    // Metadata block header: 1 byte last-flag + type, 3 bytes length.
+    let header = data
+        .get(4..8)
+        .ok_or_else(|| bad("truncated before first metadata block"))?;
+    if header[0] & 0x7f != 0 {
+        return Err(bad("first metadata block is not STREAMINFO"));
+    }
+    let length = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+    if length < 34 {
+        return Err(bad("STREAMINFO block shorter than 34 bytes"));
+    }
+    let info = data
+        .get(8..8 + 34)
+        .ok_or_else(|| bad("truncated STREAMINFO block"))?;
+
+    // STREAMINFO packs sample rate (20 bits), channels (3) and bit depth (5)
+    // across bytes 10..14, followed by a 36-bit total-sample count.
+    let sample_rate = (u32::from(info[10]) << 12) | (u32::from(info[11]) << 4) | (u32::from(info[12]) >> 4);
+    let bit_depth = (((u32::from(info[12]) & 0x01) << 4) | (u32::from(info[13]) >> 4)) + 1;
+    let total_samples = (u64::from(info[13] & 0x0f) << 32)
+        | u64::from(u32::from_be_bytes([info[14], info[15], info[16], info[17]]));
+    let mut md5 = [0u8; 16];
+    md5.copy_from_slice(&info[18..34]);
+
+    if sample_rate == 0 {
+        return Err(bad("STREAMINFO declares a zero sample rate"));
+    }
+
+    Ok(FlacInfo {
+        sample_rate,
+        bit_depth,
+        total_samples,
+        md5,
+    })
+}
+
+/// One segment's size: `Content-Length` from a HEAD request when the server
+/// reports it, otherwise a `Range: bytes=0-0` probe whose `Content-Range`
+/// response header carries the full length after the slash.
+async fn segment_size(client: &reqwest::Client, url: &str) -> Result<Option<u64>> {
+    let resp = client.head(url).send().await?;
+    if let Some(length) = resp.content_length() {
+        return Ok(Some(length));
+    }
+
+    let resp = client
+        .get(url)
+        .header(reqwest::header::RANGE, "bytes=0-0")
+        .send()
+        .await?;
+    Ok(resp
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit('/').next())
+        .and_then(|total| total.parse().ok()))
+}
+
+/// Scan the head of a downloaded FLAC stream for MQA encoder markers.
+///
+/// The actual MQA signaling lives in the least-significant bits of the
+/// decoded samples, which would take a full FLAC decoder to verify. In
+/// practice every MQA encode Tidal serves also carries an `MQAENCODER`
+/// vorbis comment (alongside `ORIGINALSAMPLERATE`) in the metadata blocks at
+/// the head of the stream, so scanning the raw bytes for that marker is a
+/// reliable signal without decoding audio. Pass the first few kilobytes of
+/// the file; anything that isn't FLAC returns `false`.
+pub fn detect_mqa_from_header(header: &[u8]) -> bool {
+    if !header.starts_with(b"fLaC") {
+        return false;
+    }
+    header
+        .windows(b"MQAENCODER".len())
+        .any(|window| window == b"MQAENCODER")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lossy_stream_info() -> StreamInfo {
+        StreamInfo {
+            track_id: 1,
+            urls: vec!["https://example.com/audio.mp4".into()],
+            mime_type: "audio/mp4".into(),
+            codecs: "mp4a.40.5".into(),
+            sample_rate: None,
+            bit_depth: None,
+            encryption: None,
+            requested_quality: "HI_RES_LOSSLESS".into(),
+            actual_quality: "LOW".into(),
+        }
+    }
+
+    /// Build a minimal valid FLAC head: marker + STREAMINFO declaring
+    /// 44.1 kHz / 16-bit / `total_samples` samples.
+    fn flac_header(total_samples: u64) -> Vec<u8> {
+        let mut data = b"fLaC".to_vec();
+        data.extend_from_slice(&[0x00, 0x00, 0x00, 0x22]);
+        let mut info = [0u8; 34];
+        info[0..2].copy_from_slice(&4096u16.to_be_bytes());
+        info[2..4].copy_from_slice(&4096u16.to_be_bytes());
+        // 44100 Hz in the top 20 bits, 2 channels, 16-bit samples.
+        info[10] = (44_100 >> 12) as u8;
+        info[11] = (44_100 >> 4) as u8;
+        // Low 4 bits of the rate, 2 channels, then 16-bit depth stored as 15
+        // with its top bit in info[12] and low nibble in info[13].
+        info[12] = (((44_100 & 0x0f) as u8) << 4) | (1 << 1);
+        info[13] = 0xf0 | ((total_samples >> 32) & 0x0f) as u8;
+        info[14..18].copy_from_slice(&((total_samples & 0xffff_ffff) as u32).to_be_bytes());
+        info[18..34].copy_from_slice(&[0xab; 16]);
+        data.extend_from_slice(&info);
+        data
+    }
+
+    #[test]
+    fn audio_quality_round_trips_and_accepts_aliases() {
+        for quality in [
+            AudioQuality::Low,
+            AudioQuality::High,
+            AudioQuality::Lossless,
+            AudioQuality::HiRes,
+            AudioQuality::HiResLossless,
+        ] {
+            assert_eq!(quality.to_string().parse::<AudioQuality>().unwrap(), quality);
+        }
+        assert_eq!("max".parse::<AudioQuality>().unwrap(), AudioQuality::HiResLossless);
+        assert_eq!("HiFi".parse::<AudioQuality>().unwrap(), AudioQuality::Lossless);
+        assert_eq!("hi-res".parse::<AudioQuality>().unwrap(), AudioQuality::HiRes);
+        assert!("ultra".parse::<AudioQuality>().is_err());
+    }
+
+    #[test]
+    fn verify_flac_parses_streaminfo() {
+        let data = flac_header(1_234_567);
+        let info = verify_flac(&data).unwrap();
+        assert_eq!(info.sample_rate, 44_100);
+        assert_eq!(info.bit_depth, 16);
+        assert_eq!(info.total_samples, 1_234_567);
+        assert_eq!(info.md5, [0xab; 16]);
+    }
+
+    #[test]
+    fn verify_flac_rejects_garbage_and_truncation() {
+        assert!(verify_flac(b"not a flac stream").is_err());
+        assert!(verify_flac(b"fLaC").is_err());
+        let truncated = &flac_header(100)[..20];
+        assert!(verify_flac(truncated).is_err());
+    }
+
+    #[test]
+    fn mqa_detection_from_metadata_and_header() {
+        let mut info = lossy_stream_info();
+        assert!(!info.is_mqa());
+
+        // Legacy HI_RES grant: MQA FLAC folded into a 48 kHz container.
+        info.codecs = "flac".into();
+        info.actual_quality = "HI_RES".into();
+        info.sample_rate = Some(48_000);
+        assert!(info.is_mqa());
+
+        // A genuine hi-res rate is not the folded MQA shape.
+        info.sample_rate = Some(96_000);
+        assert!(!info.is_mqa());
+
+        let mut mqa_file = b"fLaC\x00\x00\x00\x22".to_vec();
+        mqa_file.extend_from_slice(b"padding MQAENCODER=SW2.3 more");
+        assert!(detect_mqa_from_header(&mqa_file));
+        assert!(!detect_mqa_from_header(b"fLaC\x00plain flac header"));
+        assert!(!detect_mqa_from_header(b"ID3 not flac MQAENCODER"));
+    }
+
+    #[test]
+    fn low_quality_stream_uses_m4a_extension() {
+        let info = lossy_stream_info();
+        assert_eq!(info.file_extension(), "m4a");
+        assert!(!info.is_lossless());
+        assert_eq!(info.codec_display(), "HE-AAC");
+        assert!(info.was_downgraded());
+    }
+
+    #[test]
+    fn flac_stream_is_lossless() {
+        let mut info = lossy_stream_info();
+        info.codecs = "flac".into();
+        info.mime_type = "audio/flac".into();
+        assert_eq!(info.file_extension(), "flac");
+        assert!(info.is_lossless());
+        assert_eq!(info.codec_display(), "FLAC");
+    }
+
+    #[test]
+    fn compute_peaks_buckets_and_normalizes() {
+        let samples = [0.1, -0.5, 0.2, 0.25, -1.0, 0.0];
+        let peaks = compute_peaks(&samples, 3);
+        assert_eq!(peaks, vec![0.5, 0.25, 1.0]);
+
+        assert!(compute_peaks(&[], 4).is_empty());
+        assert!(compute_peaks(&samples, 0).is_empty());
+    }
+
+    #[test]
+    fn expected_extension_is_none_for_unknown_container() {
+        let mut info = lossy_stream_info();
+        assert_eq!(info.expected_extension(), Some("m4a"));
+
+        info.codecs = "unknown".into();
+        info.mime_type = "application/octet-stream".into();
+        assert_eq!(info.expected_extension(), None);
+    }
 }
@@ -0,0 +1,105 @@
+//! Integration tests against the live Tidal API.
+//!
+//! The network-bound tests are `#[ignore]`d by default so normal CI runs need
+//! no credentials. To exercise them, export `TIDAL_TEST_TOKEN` with a valid
+//! access token and run:
+//!
+//! ```text
+//! TIDAL_TEST_TOKEN=... cargo test -p tidal -- --ignored
+//! ```
+
+use tidal::{
+    AudioQuality,
+    TidalClient,
+    parse_mpd,
+};
+
+/// A long-lived public track ("Smells Like Teen Spirit"); update if it ever
+/// leaves the catalog.
+const TEST_TRACK_ID: u64 = 668462;
+const TEST_ALBUM_ID: u64 = 668457;
+
+fn client() -> TidalClient {
+    let token = std::env::var("TIDAL_TEST_TOKEN")
+        .expect("TIDAL_TEST_TOKEN must be set to run integration tests");
+    TidalClient::new(token, String::new(), "US".to_string())
+}
+
+#[tokio::test]
+#[ignore = "requires TIDAL_TEST_TOKEN"]
+async fn get_track_returns_expected_shape() {
+    let mut client = client();
+    let track = client.get_track(TEST_TRACK_ID).await.unwrap();
+
+    assert_eq!(track.id, TEST_TRACK_ID);
+    assert!(!track.title.is_empty());
+    assert!(track.duration > 0);
+    assert!(!track.artists.is_empty());
+}
+
+#[tokio::test]
+#[ignore = "requires TIDAL_TEST_TOKEN"]
+async fn get_album_tracks_pages_correctly() {
+    let mut client = client();
+    let page = client.get_album_tracks(TEST_ALBUM_ID, 50, 0).await.unwrap();
+
+    assert!(!page.items.is_empty());
+    assert!(page.total as usize >= page.items.len());
+    assert!(page.items.iter().all(|t| t.track_number.is_some()));
+}
+
+#[tokio::test]
+#[ignore = "requires TIDAL_TEST_TOKEN"]
+async fn search_finds_known_artist() {
+    let mut client = client();
+    let results = client.search("Nirvana", 10).await.unwrap();
+
+    let artists = results.artists.expect("artist results");
+    assert!(artists.items.iter().any(|a| a.name == "Nirvana"));
+}
+
+#[tokio::test]
+#[ignore = "requires TIDAL_TEST_TOKEN"]
+async fn get_stream_info_yields_plausible_stream() {
+    let mut client = client();
+    let info = client
+        .get_stream_info(TEST_TRACK_ID, AudioQuality::Lossless)
+        .await
+        .unwrap();
+
+    assert_eq!(info.track_id, TEST_TRACK_ID);
+    assert!(!info.urls.is_empty());
+    assert!(info.urls.iter().all(|u| u.starts_with("http")));
+    // Whatever quality was granted, the codec must be one we know how to save.
+    assert!(info.expected_extension().is_some(), "codecs: {}", info.codecs);
+}
+
+// No credentials needed: parse_mpd is pure. Kept with the integration tests
+// because the fixture mirrors what the live playbackinfo endpoint returns.
+#[test]
+fn parse_mpd_extracts_segment_urls() {
+    let mpd = r#"<?xml version="1.0" encoding="UTF-8"?>
+<MPD xmlns="urn:mpeg:dash:schema:mpd:2011" type="static">
+  <Period>
+    <AdaptationSet mimeType="audio/mp4">
+      <Representation id="0" codecs="flac">
+        <SegmentTemplate initialization="https://example.com/init.mp4" media="https://example.com/seg_$Number$.mp4">
+          <SegmentTimeline>
+            <S d="40000" r="2"/>
+            <S d="20000"/>
+          </SegmentTimeline>
+        </SegmentTemplate>
+      </Representation>
+    </AdaptationSet>
+  </Period>
+</MPD>"#;
+
+    let manifest = parse_mpd(mpd).unwrap();
+    assert_eq!(manifest.codecs, "flac");
+    assert_eq!(manifest.mime_type, "audio/mp4");
+    // init segment + 3 repeated + 1 single
+    assert_eq!(manifest.urls.len(), 5);
+    assert_eq!(manifest.urls[0], "https://example.com/init.mp4");
+    assert_eq!(manifest.urls[1], "https://example.com/seg_1.mp4");
+    assert_eq!(manifest.urls[4], "https://example.com/seg_4.mp4");
+}